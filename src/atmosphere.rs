@@ -0,0 +1,25 @@
+//! Provides the International Standard Atmosphere.
+
+use crate::constants::*;
+
+/// Temperature lapse rate in the troposphere (in K/m).
+const LAPSE_RATE: f64 = 0.0065;
+
+/// Altitude of the tropopause (in m).
+const TROPOPAUSE: f64 = 11_000.0;
+
+/// Gravitational acceleration (in m/s2).
+pub const G0: f64 = 9.80665;
+
+/// Computes the static temperature (in K) and pressure (in Pa) at a given geopotential altitude (in m).
+///
+/// Valid up to 20 km.
+pub fn isa(altitude: f64) -> (f64, f64) {
+    if altitude <= TROPOPAUSE {
+        let t = T0 - LAPSE_RATE*altitude;
+        (t, P0 * (t/T0).powf(G0/(LAPSE_RATE*R)))
+    } else {
+        let (t, p) = isa(TROPOPAUSE);
+        (t, p * (-G0*(altitude - TROPOPAUSE)/(R*t)).exp())
+    }
+}
\ No newline at end of file
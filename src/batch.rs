@@ -0,0 +1,229 @@
+//! Evaluates a batch of designs and writes the results to CSV.
+//!
+//! Designs are either generated by a design of experiments or read from a CSV
+//! file whose header names the variables given in each row:
+//!
+//! ```text
+//! fan_bypass,fan_pressure_ratio,hpt_inlet_temp
+//! 6.0,1.45,1650
+//! 8.0,1.40,
+//! ```
+//!
+//! Empty cells and missing columns keep the value of the base design.  Each
+//! output row holds the inputs, then the design-point outputs, the efficiency
+//! of each turbomachine and the total temperature and pressure at each station,
+//! then one flag per requirement that is `1` when the design violates it.
+//! Designs whose cycle could not be computed are flagged as failed and
+//! infeasible.
+
+use std::{
+    fs,
+    path::Path,
+};
+
+use crate::{
+    parallel,
+    Bounds,
+    Emissions,
+    Engine,
+    Noise,
+    Requirements,
+    Variables,
+    VarSelector,
+};
+
+/// Names of the output columns, after the inputs and before the requirement flags.
+const OUTPUTS: [&str; 11] = [
+    "thrust",
+    "sfc",
+    "mass_flow",
+    "fuel_flow",
+    "overall_pressure_ratio",
+    "compressor_exit_temp",
+    "turbine_inlet_temp",
+    "nox_index",
+    "jet_noise",
+    "dry_weight",
+    "engine_length",
+];
+
+#[derive(Clone, Debug)]
+/// Holds the evaluated rows of a batch.
+pub struct Batch {
+    /// Column names
+    pub header: Vec<String>,
+
+    /// Values of each row, in the order of the header
+    pub rows: Vec<Vec<String>>,
+
+    /// Number of samples whose cycle could not be computed
+    pub failed: usize,
+
+    /// Number of samples that meet every requirement
+    pub feasible: usize,
+}
+
+impl Batch {
+    /// Reads designs from a CSV file, one per row, on top of a base design.
+    ///
+    /// Returns the variables named in the header and the design of each row.
+    pub fn load_designs(path: &Path, base: Variables, bounds: &Bounds) -> Result<(Vec<VarSelector>, Vec<Variables>), String> {
+        let text = fs::read_to_string(path)
+            .map_err(|_| format!("Could not read designs {}", path.display()))?;
+
+        Self::parse_designs(&text, base, bounds).map_err(|error| format!("In designs {}: {}", path.display(), error))
+    }
+
+    /// Parses designs written as CSV, one per row, on top of a base design.
+    fn parse_designs(text: &str, base: Variables, bounds: &Bounds) -> Result<(Vec<VarSelector>, Vec<Variables>), String> {
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        let inputs = lines.next().unwrap_or_default()
+            .split(',')
+            .map(|name| name.trim().trim_matches('"').parse())
+            .collect::<Result<Vec<VarSelector>, String>>()?;
+
+        let designs = lines.enumerate().map(|(row, line)| {
+            let cells = line.split(',').map(|cell| cell.trim().trim_matches('"')).collect::<Vec<&str>>();
+            if cells.len() != inputs.len() {
+                return Err (format!("Design {} has {} values but the header names {} variables", row + 1, cells.len(), inputs.len()));
+            }
+
+            let mut design = base;
+            for (&var, cell) in inputs.iter().zip(cells).filter(|(_, cell)| !cell.is_empty()) {
+                let value = str::parse::<f64>(cell)
+                    .map_err(|_| format!("Could not parse {} as numeric value in design {}", cell, row + 1))?;
+
+                bounds.check(var, value)?;
+                *design.get_reference(var) = value;
+            }
+            Ok (design)
+        }).collect::<Result<Vec<Variables>, String>>()?;
+
+        if designs.is_empty() {
+            return Err ("No designs found".to_string());
+        }
+
+        Ok ((inputs, designs))
+    }
+
+    /// Evaluates each sample, recording the selected inputs, the outputs, the station data and the requirement flags.
+    pub fn evaluate<E: Engine + ?Sized>(engine: &E, requirements: &Requirements, inputs: &[VarSelector], samples: &[Variables]) -> Self {
+        let results = parallel::map(samples, |&variables| {
+            let mut sample = variables;
+            let mut row = inputs.iter().map(|&var| *sample.get_reference(var)).collect::<Vec<f64>>();
+
+            // Every output is taken from a single cycle of the design
+            let cycle = engine.cycle(variables);
+            let (thrust, sfc) = engine.analyze_cycle(&cycle);
+            let temperature = |name: &str| cycle.stations.iter().find(|s| s.name == name).map_or(f64::NAN, |s| s.total_temp);
+            let size = engine.size_cycle(variables, &cycle);
+
+            row.extend([
+                thrust,
+                sfc,
+                cycle.mass_flow,
+                cycle.fuel_flow,
+                engine.overall_pressure_ratio(variables),
+                temperature("3"),
+                temperature("4"),
+                Emissions::new(&cycle, engine.fuel()).map_or(f64::NAN, |e| e.nox_index),
+                Noise::new(&cycle).map_or(f64::NAN, |noise| noise.level),
+                size.as_ref().map_or(f64::NAN, |size| size.weight),
+                size.as_ref().map_or(f64::NAN, |size| size.length),
+            ]);
+
+            // Efficiency and station columns depend on the engine architecture
+            let mut details = Vec::new();
+            for efficiency in &cycle.efficiencies {
+                let name = efficiency.name.to_lowercase();
+                details.push((format!("{}_isentropic_efficiency", name), efficiency.isentropic));
+                details.push((format!("{}_polytropic_efficiency", name), efficiency.polytropic));
+            }
+            for station in &cycle.stations {
+                details.push((format!("total_temp_{}", station.name), station.total_temp));
+                details.push((format!("total_pressure_{}", station.name), station.total_pressure));
+            }
+
+            let failed = !thrust.is_finite() || !sfc.is_finite();
+            let checks = requirements.check_cycle(engine, variables, &cycle)
+                .into_iter()
+                .map(|(name, violation)| (name, violation.is_some()))
+                .collect::<Vec<(&'static str, bool)>>();
+            let feasible = !failed && checks.iter().all(|&(_, violated)| !violated);
+
+            (row, details, checks, failed, feasible)
+        });
+
+        // Take the efficiency and station columns of every design, in order of appearance
+        let mut columns: Vec<String> = Vec::new();
+        for (_, details, _, _, _) in &results {
+            for (name, _) in details {
+                if !columns.contains(name) {
+                    columns.push(name.clone());
+                }
+            }
+        }
+
+        let mut header = inputs.iter().map(|var| var.key().to_string()).collect::<Vec<String>>();
+        header.extend(OUTPUTS.iter().map(|s| s.to_string()));
+        header.extend(columns.iter().cloned());
+        if let Some ((_, _, checks, _, _)) = results.first() {
+            header.extend(checks.iter().map(|(name, _)| format!("violates_{}", name)));
+        }
+        header.extend(["failed".to_string(), "feasible".to_string()]);
+
+        let flag = |set: bool| if set { "1" } else { "0" }.to_string();
+        let rows = results.iter().map(|(row, details, checks, failed, feasible)| {
+            let mut values = row.iter().map(|value| format!("{}", value)).collect::<Vec<String>>();
+            values.extend(columns.iter().map(|column| {
+                let value = details.iter().find(|(name, _)| name == column).map_or(f64::NAN, |&(_, value)| value);
+                format!("{}", value)
+            }));
+            values.extend(checks.iter().map(|&(_, violated)| flag(violated)));
+            values.push(flag(*failed));
+            values.push(flag(*feasible));
+            values
+        }).collect();
+
+        Self {
+            header,
+            rows,
+            failed: results.iter().filter(|r| r.3).count(),
+            feasible: results.iter().filter(|r| r.4).count(),
+        }
+    }
+
+    /// Writes this batch to a CSV file.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let mut text = self.header.join(",");
+        text.push('\n');
+        for row in &self.rows {
+            text.push_str(&row.join(","));
+            text.push('\n');
+        }
+
+        fs::write(path, text).map_err(|_| format!("Could not write results {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::VANILLA_PLUS;
+
+    #[test]
+    fn empty_cells_keep_base_design() {
+        let (inputs, designs) = Batch::parse_designs("fan_bypass,hpt_inlet_temp\n6.0,1650\n8.0,\n", VANILLA_PLUS, &Bounds::default()).unwrap();
+
+        assert_eq!(inputs, [VarSelector::FanBypass, VarSelector::HptInletTemp]);
+        assert_eq!(designs.len(), 2);
+        assert_eq!((designs[0].fan_bypass, designs[0].hpt_inlet_temp), (6.0, 1650.0));
+        assert_eq!((designs[1].fan_bypass, designs[1].hpt_inlet_temp), (8.0, VANILLA_PLUS.hpt_inlet_temp));
+    }
+
+    #[test]
+    fn rejects_bad_designs() {
+        let error = Batch::parse_designs("fan_bypass,hpt_inlet_temp\n6.0\n", VANILLA_PLUS, &Bounds::default()).unwrap_err();
+        assert_eq!(error, "Design 1 has 1 values but the header names 2 variables");
+    }
+}
@@ -0,0 +1,217 @@
+//! Holds the bounds on the free variables.
+//!
+//! Bounds default to the constants in `constants.rs` and may be changed at
+//! runtime, either one at a time with `--bound fan_bypass=4..12` or from a text
+//! file with one bound per line in the same form.  Lines starting with `#` are
+//! comments:
+//!
+//! ```text
+//! # Design space
+//! fan_bypass = 4..12
+//! fan_pressure_ratio = 1.3..1.7
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    process,
+};
+
+use crate::{
+    constants::*,
+    Variables,
+    VarSelector,
+};
+
+#[derive(Clone, Debug)]
+/// Holds the lower and upper bound of each bounded variable.
+pub struct Bounds {
+    /// Lower and upper bounds, keyed by variable
+    pub limits: HashMap<VarSelector, (f64, f64)>,
+}
+
+impl Default for Bounds {
+    /// Constructs the bounds given by `constants.rs`.
+    fn default() -> Self {
+        use VarSelector::*;
+
+        let limits = [
+            (InletMachNumber, INLET_MACH_NUMBER_MIN, INLET_MACH_NUMBER_MAX),
+            (InletDiameter, INLET_DIAMETER_MIN, INLET_DIAMETER_MAX),
+            (FanPressureRatio, FAN_PRESSURE_RATIO_MIN, FAN_PRESSURE_RATIO_MAX),
+            (FanBypass, FAN_BYPASS_MIN, FAN_BYPASS_MAX),
+            (LpcPressureRatio, LPC_PRESSURE_RATIO_MIN, LPC_PRESSURE_RATIO_MAX),
+            (IpcPressureRatio, IPC_PRESSURE_RATIO_MIN, IPC_PRESSURE_RATIO_MAX),
+            (HpcPressureRatio, HPC_PRESSURE_RATIO_MIN, HPC_PRESSURE_RATIO_MAX),
+            (HpcDischargeTemp, HPC_DISCHARGE_TEMP_MIN, HPC_DISCHARGE_TEMP_MAX),
+            (HptInletTemp, HPT_INLET_TEMP_MIN, HPT_INLET_TEMP_MAX),
+            (BurnerExitTemp, BURNER_EXIT_TEMP_MIN, BURNER_EXIT_TEMP_MAX),
+            (InletEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (FanEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (GearboxEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (LpcEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (IpcEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (HpcEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (CombustorEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (HptEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (IptEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (LptEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (MixerEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (AfterburnerEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (PowerTurbineEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (PropellerEfficiency, EFFICIENCY_MIN, EFFICIENCY_MAX),
+            (CombustorPressureRecovery, PRESSURE_RECOVERY_MIN, PRESSURE_RECOVERY_MAX),
+            (BypassPressureRecovery, PRESSURE_RECOVERY_MIN, PRESSURE_RECOVERY_MAX),
+            (AfterburnerPressureRecovery, PRESSURE_RECOVERY_MIN, PRESSURE_RECOVERY_MAX),
+            (FuelDeltaH, FUEL_DELTA_H_MIN, FUEL_DELTA_H_MAX),
+            (FuelCp, FUEL_CP_MIN, FUEL_CP_MAX),
+            (NgvCoolingFraction, BLEED_FRACTION_MIN, BLEED_FRACTION_MAX),
+            (RotorCoolingFraction, BLEED_FRACTION_MIN, BLEED_FRACTION_MAX),
+            (CustomerBleedFraction, BLEED_FRACTION_MIN, BLEED_FRACTION_MAX),
+            (HpPowerOfftake, POWER_OFFTAKE_MIN, POWER_OFFTAKE_MAX),
+            (LpPowerOfftake, POWER_OFFTAKE_MIN, POWER_OFFTAKE_MAX),
+        ];
+
+        Self {
+            limits: limits.iter().map(|&(var, min, max)| (var, (min, max))).collect(),
+        }
+    }
+}
+
+impl Bounds {
+    /// Reads bounds from a file, on top of the default bounds.
+    pub fn load(&mut self, path: &Path) {
+        let text = match fs::read_to_string(path) {
+            Ok (text) => text,
+            Err (_) => {
+                println!("[FATAL] Could not read bounds {}", path.display());
+                process::exit(0);
+            },
+        };
+
+        for line in text.lines() {
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                self.set(line);
+            }
+        }
+    }
+
+    /// Sets one bound from a specification of the form `variable=min..max`.
+    pub fn set(&mut self, spec: &str) {
+        let (var, limits) = Self::parse(spec).unwrap_or_else(|error| {
+            println!("[FATAL] {}", error);
+            process::exit(0);
+        });
+
+        self.limits.insert(var, limits);
+    }
+
+    /// Parses one bound of the form `variable=min..max`.
+    fn parse(spec: &str) -> Result<(VarSelector, (f64, f64)), String> {
+        let spec = spec.split_whitespace().collect::<String>();
+        let (name, (min, max)) = spec.split_once('=')
+            .and_then(|(name, range)| Some ((name, range.split_once("..")?)))
+            .ok_or_else(|| format!("Could not parse bound {}.  Use `variable=min..max`.", spec))?;
+
+        let parse = |value: &str| str::parse::<f64>(value)
+            .map_err(|_| format!("Could not parse {} as numeric value in bound {}", value, spec));
+        let (min, max) = (parse(min)?, parse(max)?);
+
+        if min > max {
+            return Err (format!("Lower bound {} is above upper bound {} in bound {}", min, max, spec));
+        }
+
+        Ok ((name.parse()?, (min, max)))
+    }
+
+    /// Gives the finite lower and upper bounds of each of a set of variables.
+    pub fn ranges(&self, vars: &[VarSelector]) -> Result<Vec<(f64, f64)>, String> {
+        vars.iter().map(|var| match self.limits.get(var) {
+            Some (&(min, max)) if min.is_finite() && max.is_finite() => Ok ((min, max)),
+            _ => {
+                let name: String = (*var).into();
+                Err (format!("{} needs finite bounds.  Use `--vary [VARIABLE]=[MIN]..[MAX]` or `--bound`.", name))
+            },
+        }).collect()
+    }
+
+    /// Clamps each bounded variable to its bounds.
+    pub fn clamp(&self, mut variables: Variables) -> Variables {
+        for (&var, &(min, max)) in &self.limits {
+            let value = variables.get_reference(var);
+            *value = value.clamp(min, max);
+        }
+
+        variables
+    }
+
+    /// Ensures that a value given for a variable lies within its bounds.
+    pub fn validate(&self, var: VarSelector, value: f64) {
+        if let Err (error) = self.check(var, value) {
+            println!("[FATAL] {}", error);
+            process::exit(0);
+        }
+    }
+
+    /// Checks that a value given for a variable lies within its bounds.
+    pub fn check(&self, var: VarSelector, value: f64) -> Result<(), String> {
+        if let Some (&(min, max)) = self.limits.get(&var) {
+            if value < min || value > max {
+                let name: String = var.into();
+                return Err (format!("{} of {} is outside its bounds of {} to {}.  Use `--bound` to change them.", name, value, min, max));
+            }
+        }
+
+        Ok (())
+    }
+
+    /// Checks that every bounded variable of a design lies within its bounds.
+    pub fn check_design(&self, mut variables: Variables) -> Result<(), String> {
+        let mut vars = self.limits.keys().copied().collect::<Vec<VarSelector>>();
+        vars.sort_by_key(|var| var.key());
+
+        for var in vars {
+            self.check(var, *variables.get_reference(var))?;
+        }
+
+        Ok (())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bound() {
+        assert_eq!(Bounds::parse("fan_bypass = 4..12"), Ok ((VarSelector::FanBypass, (4.0, 12.0))));
+    }
+
+    #[test]
+    fn checks_designs() {
+        let bounds = Bounds::default();
+        assert_eq!(bounds.check_design(VANILLA_PLUS), Ok (()));
+        assert_eq!(bounds.check_design(VANILLA_PLUS_THREE_SPOOL), Ok (()));
+
+        let mut design = VANILLA_PLUS;
+        design.fan_bypass = 2.0 * FAN_BYPASS_MAX;
+        assert!(bounds.check_design(design).is_err());
+    }
+
+    #[test]
+    fn gives_finite_ranges() {
+        let bounds = Bounds::default();
+
+        assert_eq!(bounds.ranges(&[VarSelector::FanBypass]), Ok (vec![(FAN_BYPASS_MIN, FAN_BYPASS_MAX)]));
+        assert!(bounds.ranges(&[VarSelector::HpPowerOfftake]).is_err());
+        assert!(bounds.ranges(&[VarSelector::GearRatio]).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_bounds() {
+        let error = Bounds::parse("fan_bypass=12..4").unwrap_err();
+        assert_eq!(error, "Lower bound 12 is above upper bound 4 in bound fan_bypass=12..4");
+    }
+}
\ No newline at end of file
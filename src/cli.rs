@@ -2,15 +2,54 @@
 
 use std::{
     env,
+    path::Path,
     process,
 };
 
 use crate::{
     constants::*,
+    parallel,
+    Batch,
+    Bounds,
+    BleedStation,
+    Cooling,
+    Condition,
+    Design,
+    Engine,
+    Exhaust,
+    Experiment,
+    Fuel,
+    FUELS,
+    GasModel,
+    Maps,
+    Mission,
+    Objective,
+    Polytropic,
+    Ramjet,
+    Requirements,
+    Sensitivity,
+    Spools,
+    Throttle,
+    Turbofan,
+    Turbojet,
+    Turboprop,
+    Turboshaft,
+    Uncertainty,
     Variables,
     VarSelector,
 };
 
+/// Flags that only apply to the turbofan.
+const TURBOFAN_FLAGS: &[&str] = &[
+    "--exhaust",
+    "--spools",
+    "--no-booster",
+    "--geared",
+    "--polytropic",
+    "--cooling",
+    "--bleed-station",
+];
+
 /// Holds command-line inputs.
 pub enum Cli {
     Plot {
@@ -18,18 +57,97 @@ pub enum Cli {
         left: f64,
         right: f64,
         allvars: Variables,
+        engine: Box<dyn Engine>,
     },
     Optimize {
         allvars: Variables,
+        engine: Box<dyn Engine>,
+        objective: Objective,
+        requirements: Requirements,
+        bounds: Bounds,
+    },
+    Analyze {
+        allvars: Variables,
+        engine: Box<dyn Engine>,
+        requirements: Requirements,
+    },
+    OffDesign {
+        allvars: Variables,
+        engine: Box<dyn Engine>,
+        maps: Box<Maps>,
+        condition: Condition,
+    },
+    Throttle {
+        setting: fn(f64) -> Throttle,
+        left: f64,
+        right: f64,
+        allvars: Variables,
+        engine: Box<dyn Engine>,
+        maps: Box<Maps>,
+        condition: Condition,
+    },
+    Installed {
+        allvars: Variables,
+        engine: Box<dyn Engine>,
+        maps: Box<Maps>,
+        condition: Condition,
+    },
+    Mission {
+        allvars: Variables,
+        engine: Box<dyn Engine>,
+        maps: Box<Maps>,
+        mission: Mission,
+    },
+    Uncertainty {
+        allvars: Variables,
+        engine: Box<dyn Engine>,
+        uncertainty: Uncertainty,
+        bounds: Bounds,
+    },
+    Sensitivity {
+        allvars: Variables,
+        engine: Box<dyn Engine>,
+        sensitivity: Sensitivity,
+        bounds: Bounds,
+    },
+    Doe {
+        allvars: Variables,
+        engine: Box<dyn Engine>,
+        experiment: Experiment,
+        requirements: Requirements,
+        bounds: Bounds,
+        output: String,
+    },
+    Batch {
+        engine: Box<dyn Engine>,
+        inputs: Vec<VarSelector>,
+        designs: Vec<Variables>,
+        requirements: Requirements,
+        output: String,
     },
 }
 
+impl Default for Cli {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Cli {
     /// Reads input from CLI.
     pub fn new() -> Self {
-        let args = env::args().collect::<Vec<String>>();
+        let (requirements, bounds, args) = Self::parse_limits(env::args().collect());
+
+        if args.len() < 2 {
+            Self::help();
+        }
 
         if &args[1] == "plot" {
+            if args.len() < 5 {
+                println!("[FATAL] Please specify a variable and its bounds");
+                process::exit(0);
+            }
+
             let variable: VarSelector = args[2].clone().into();
             
             let left = match str::parse::<f64>(&args[3]) {
@@ -47,99 +165,271 @@ impl Cli {
                 }
             };
 
-            // Initialize variables
-            let mut allvars = VANILLA_PLUS;
+            let (allvars, engine) = Self::parse_flags(&args, 5, &bounds);
 
-            let mut i = 5;
-            while i < args.len() {
-                let arg = &args[i];
+            if !matches!(variable, VarSelector::FlightMachNumber) {
+                Self::check_flight_mach(&allvars, engine.as_ref());
+            }
 
-                if arg == "--fix" {
-                    i += 1;
-                    let arg = &args[i];
+            Self::Plot {
+                variable,
+                left,
+                right,
+                allvars,
+                engine,
+            }
+        } else if &args[1] == "optimize" {
+            // Skip the optional objective variable
+            let mut i = 2;
+            while i < args.len() && !args[i].starts_with("--") {
+                i += 1;
+            }
 
-                    // Stop when you find another flag
-                    while !(&args[i]).starts_with("--") {
-                        let option: VarSelector = args[i].clone().into();
+            let (mut mission, maps, rest) = Self::parse_mission(&args, i);
+            mission.engine_count = requirements.engine_count;
 
-                        if args.len() == i + 1 {
-                            println!("[FATAL] Please specify a value for {}", arg);
+            // Select the optimization objective
+            let mut objective = "sfc";
+            let mut noise_weight = 1.0;
+            let mut design = Vec::new();
+            let mut j = 0;
+            while j < rest.len() {
+                if rest[j] == "--objective" {
+                    if j + 1 == rest.len() {
+                        println!("[FATAL] Please specify an objective");
+                        process::exit(0);
+                    }
+
+                    objective = match rest[j + 1].as_str() {
+                        "sfc" => "sfc",
+                        "fuel" => "fuel",
+                        "weight" => "weight",
+                        "noise" => "noise",
+                        _ => {
+                            println!("[FATAL] Invalid objective {}.  Type `jetlab` for help.", &rest[j + 1]);
                             process::exit(0);
-                        }
-
-                        let value = match str::parse::<f64>(&args[i + 1]) {
-                            Ok (f) => f,
-                            Err (_) => {
-                                println!("[FATAL] Could not parse {} as numeric value", &args[i + 1]);
-                                process::exit(0);
-                            },
-                        };
-
-                        *allvars.get_reference(option) = value;
-
-                        i += 2;
-                        if i >= args.len() {
-                            break;
-                        }
+                        },
+                    };
+                    j += 2;
+                } else if rest[j] == "--noise-weight" {
+                    noise_weight = Self::parse_value(&rest, j + 1);
+                    if !(0.0..=1.0).contains(&noise_weight) {
+                        println!("[FATAL] Noise weight of {} is outside 0 to 1", noise_weight);
+                        process::exit(0);
                     }
+                    j += 2;
                 } else {
-                    println!("[ERROR] Unrecognized flag {}.  Type `jetlab` for help.", arg);
+                    design.push(rest[j].clone());
+                    j += 1;
                 }
+            }
 
-                i += 1;
+            let (allvars, engine) = Self::parse_flags(&design, 0, &bounds);
+            Self::check_flight_mach(&allvars, engine.as_ref());
+
+            // Block fuel is found by flying the engine off-design
+            if objective == "fuel" {
+                Self::check_off_design(engine.as_ref());
             }
 
-            Self::Plot {
-                variable,
+            let objective = match objective {
+                "fuel" => Objective::Fuel {
+                    mission,
+                    maps,
+                },
+                "weight" => Objective::Weight,
+                "noise" => Objective::noise(engine.as_ref(), noise_weight, allvars),
+                _ => Objective::Sfc,
+            };
+
+            Self::Optimize {
+                allvars,
+                engine,
+                objective,
+                requirements,
+                bounds,
+            }
+        } else if &args[1] == "analyze" {
+            let (allvars, engine) = Self::parse_flags(&args, 2, &bounds);
+            Self::check_flight_mach(&allvars, engine.as_ref());
+
+            Self::Analyze {
+                allvars,
+                engine,
+                requirements,
+            }
+        } else if &args[1] == "offdesign" {
+            let (altitude, mach, throttle, maps, design) = Self::parse_condition(&args, 2);
+            let (allvars, engine) = Self::parse_flags(&design, 0, &bounds);
+            Self::check_off_design(engine.as_ref());
+
+            Self::OffDesign {
+                allvars,
+                engine,
+                maps,
+                condition: Condition {
+                    altitude,
+                    mach,
+                    throttle: throttle.unwrap_or(Throttle::Temperature (allvars.hpt_inlet_temp)),
+                },
+            }
+        } else if &args[1] == "installed" {
+            let (altitude, mach, throttle, maps, design) = Self::parse_condition(&args, 2);
+            let (allvars, engine) = Self::parse_flags(&design, 0, &bounds);
+            Self::check_off_design(engine.as_ref());
+
+            Self::Installed {
+                allvars,
+                engine,
+                maps,
+                condition: Condition {
+                    altitude,
+                    mach,
+                    throttle: throttle.unwrap_or(Throttle::Temperature (allvars.hpt_inlet_temp)),
+                },
+            }
+        } else if &args[1] == "throttle" {
+            if args.len() < 5 {
+                println!("[FATAL] Please specify a throttle and its bounds");
+                process::exit(0);
+            }
+
+            let setting: fn(f64) -> Throttle = match args[2].as_str() {
+                "t4" => Throttle::Temperature,
+                "fuel" => Throttle::FuelFlow,
+                _ => {
+                    println!("[FATAL] Invalid throttle {}.  Type `jetlab` for help.", &args[2]);
+                    process::exit(0);
+                },
+            };
+            let left = Self::parse_value(&args, 3);
+            let right = Self::parse_value(&args, 4);
+
+            let (altitude, mach, _, maps, design) = Self::parse_condition(&args, 5);
+            let (allvars, engine) = Self::parse_flags(&design, 0, &bounds);
+            Self::check_off_design(engine.as_ref());
+
+            Self::Throttle {
+                setting,
                 left,
                 right,
                 allvars,
+                engine,
+                maps,
+                condition: Condition {
+                    altitude,
+                    mach,
+                    throttle: setting(left),
+                },
             }
-        } else if &args[1] == "optimize" {
-            // Initialize variables
-            let mut allvars = VANILLA_PLUS;
+        } else if &args[1] == "mission" {
+            let (mut mission, maps, design) = Self::parse_mission(&args, 2);
+            mission.engine_count = requirements.engine_count;
+            let (allvars, engine) = Self::parse_flags(&design, 0, &bounds);
+            Self::check_off_design(engine.as_ref());
 
-            let mut i = 4;
-            while i < args.len() {
-                let arg = &args[i];
+            Self::Mission {
+                allvars,
+                engine,
+                maps,
+                mission,
+            }
+        } else if &args[1] == "uncertainty" {
+            let (samples, seed, specs, design) = Self::parse_sampling(&args, 2, 1000);
 
-                if arg == "--fix" {
-                    i += 1;
-                    let arg = &args[i];
+            let mut uncertainty = Uncertainty {
+                inputs: Vec::new(),
+                samples,
+                seed,
+            };
+            for spec in &specs {
+                uncertainty.vary(spec);
+            }
 
-                    // Stop when you find another flag
-                    while !(&args[i]).starts_with("--") {
-                        let option: VarSelector = args[i].clone().into();
+            let (allvars, engine) = Self::parse_flags(&design, 0, &bounds);
+            Self::check_flight_mach(&allvars, engine.as_ref());
 
-                        if args.len() == i + 1 {
-                            println!("[FATAL] Please specify a value for {}", arg);
-                            process::exit(0);
-                        }
-
-                        let value = match str::parse::<f64>(&args[i + 1]) {
-                            Ok (f) => f,
-                            Err (_) => {
-                                println!("[FATAL] Could not parse {} as numeric value", &args[i + 1]);
-                                process::exit(0);
-                            },
-                        };
-
-                        *allvars.get_reference(option) = value;
-
-                        i += 2;
-                        if i >= args.len() {
-                            break;
-                        }
-                    }
-                } else {
-                    println!("[ERROR] Unrecognized flag {}.  Type `jetlab` for help.", arg);
-                }
+            Self::Uncertainty {
+                allvars,
+                engine,
+                uncertainty,
+                bounds,
+            }
+        } else if &args[1] == "sensitivity" {
+            let (samples, seed, specs, design) = Self::parse_sampling(&args, 2, 1000);
 
-                i += 1;
+            let mut bounds = bounds;
+            let inputs = Self::parse_inputs(&specs, &mut bounds);
+
+            let (allvars, engine) = Self::parse_flags(&design, 0, &bounds);
+            Self::check_flight_mach(&allvars, engine.as_ref());
+
+            Self::Sensitivity {
+                allvars,
+                engine,
+                sensitivity: Sensitivity {
+                    inputs,
+                    samples,
+                    seed,
+                },
+                bounds,
             }
+        } else if &args[1] == "doe" {
+            let design: Design = match args.get(2) {
+                Some (design) => design.to_string().into(),
+                None => {
+                    println!("[FATAL] Please specify a design (factorial, lhs or sobol)");
+                    process::exit(0);
+                },
+            };
+            let default_samples = if design == Design::Factorial { 5 } else { 100 };
+            let (samples, seed, specs, rest) = Self::parse_sampling(&args, 3, default_samples);
 
-            Self::Optimize {
+            let (output, flags) = Self::parse_output(&rest, "doe.csv");
+
+            let mut bounds = bounds;
+            let inputs = Self::parse_inputs(&specs, &mut bounds);
+
+            let (allvars, engine) = Self::parse_flags(&flags, 0, &bounds);
+            Self::check_flight_mach(&allvars, engine.as_ref());
+
+            Self::Doe {
                 allvars,
+                engine,
+                experiment: Experiment {
+                    design,
+                    inputs,
+                    samples,
+                    seed,
+                },
+                requirements,
+                bounds,
+                output,
+            }
+        } else if &args[1] == "batch" {
+            let input = match args.get(2) {
+                Some (file) if !file.starts_with("--") => file.to_string(),
+                _ => {
+                    println!("[FATAL] Please specify a CSV file of designs");
+                    process::exit(0);
+                },
+            };
+
+            let (output, flags) = Self::parse_output(&args[3..], "batch.csv");
+            let (allvars, engine) = Self::parse_flags(&flags, 0, &bounds);
+            Self::check_flight_mach(&allvars, engine.as_ref());
+
+            let (inputs, designs) = Batch::load_designs(Path::new(&input), allvars, &bounds).unwrap_or_else(|error| {
+                println!("[FATAL] {}", error);
+                process::exit(0);
+            });
+
+            Self::Batch {
+                engine,
+                inputs,
+                designs,
+                requirements,
+                output,
             }
         } else {
             println!("[FATAL] Unrecognized subcommand {}", &args[1]);
@@ -148,6 +438,477 @@ impl Cli {
         }
     }
 
+    /// Reads the optional sample count and the `--vary` and `--seed` flags following a subcommand, starting from argument `i`.
+    ///
+    /// Returns the sample count, the seed, the `--vary` specifications and the remaining (design) flags.
+    fn parse_sampling(args: &[String], mut i: usize, samples: usize) -> (usize, u64, Vec<String>, Vec<String>) {
+        let mut samples = samples;
+        let mut seed = 0;
+        let mut specs = Vec::new();
+        let mut design = Vec::new();
+
+        if i < args.len() && !args[i].starts_with("--") {
+            samples = match str::parse::<usize>(&args[i]) {
+                Ok (n) if n > 1 => n,
+                _ => {
+                    println!("[FATAL] Could not parse {} as a number of samples above 1", &args[i]);
+                    process::exit(0);
+                },
+            };
+            i += 1;
+        }
+
+        while i < args.len() {
+            if args[i] == "--vary" {
+                i += 1;
+
+                // Stop when you find another flag
+                while i < args.len() && !args[i].starts_with("--") {
+                    specs.push(args[i].clone());
+                    i += 1;
+                }
+            } else if args[i] == "--seed" {
+                seed = match args.get(i + 1).map(|s| str::parse::<u64>(s)) {
+                    Some (Ok (seed)) => seed,
+                    _ => {
+                        println!("[FATAL] Please specify a non-negative integer seed");
+                        process::exit(0);
+                    },
+                };
+                i += 2;
+            } else {
+                design.push(args[i].clone());
+                i += 1;
+            }
+        }
+
+        if specs.is_empty() {
+            println!("[FATAL] Please specify one or more variables with `--vary`");
+            process::exit(0);
+        }
+
+        (samples, seed, specs, design)
+    }
+
+    /// Reads the variables given to `--vary` as `variable` or `variable=min..max`, setting the bounds of the latter.
+    fn parse_inputs(specs: &[String], bounds: &mut Bounds) -> Vec<VarSelector> {
+        specs.iter().map(|spec| {
+            if spec.contains('=') {
+                bounds.set(spec);
+            }
+            let name = spec.split('=').next().unwrap_or_default();
+            name.to_string().into()
+        }).collect()
+    }
+
+    /// Reads the `--output` flag, falling back to a default file.
+    ///
+    /// Returns the output file and the remaining (design) flags.
+    fn parse_output(args: &[String], default: &str) -> (String, Vec<String>) {
+        let mut output = default.to_string();
+        let mut design = Vec::new();
+
+        let mut i = 0;
+        while i < args.len() {
+            if args[i] == "--output" {
+                output = match args.get(i + 1) {
+                    Some (file) => file.to_string(),
+                    None => {
+                        println!("[FATAL] Please specify an output file");
+                        process::exit(0);
+                    },
+                };
+                i += 2;
+            } else {
+                design.push(args[i].clone());
+                i += 1;
+            }
+        }
+
+        (output, design)
+    }
+
+    /// Reads the flight condition flags following a subcommand, starting from argument `i`.
+    ///
+    /// Returns the altitude, flight Mach number, throttle setting, maps and the remaining (design) flags.
+    fn parse_condition(args: &[String], mut i: usize) -> (f64, f64, Option<Throttle>, Box<Maps>, Vec<String>) {
+        let mut altitude = 0.0;
+        let mut mach = 0.0;
+        let mut throttle = None;
+        let mut maps = Box::<Maps>::default();
+        let mut design = Vec::new();
+
+        while i < args.len() {
+            let arg = &args[i];
+            i += 1;
+
+            if arg == "--altitude" {
+                altitude = Self::parse_value(args, i);
+                i += 1;
+            } else if arg == "--mach" {
+                mach = Self::parse_value(args, i);
+                i += 1;
+            } else if arg == "--throttle" {
+                if i == args.len() {
+                    println!("[FATAL] Please specify a throttle setting");
+                    process::exit(0);
+                }
+
+                let value = Self::parse_value(args, i + 1);
+                throttle = match args[i].as_str() {
+                    "t4" => Some (Throttle::Temperature (value)),
+                    "fuel" => Some (Throttle::FuelFlow (value)),
+                    _ => {
+                        println!("[FATAL] Invalid throttle {}.  Type `jetlab` for help.", &args[i]);
+                        process::exit(0);
+                    },
+                };
+                i += 2;
+            } else if arg == "--maps" {
+                if i == args.len() {
+                    println!("[FATAL] Please specify a map directory");
+                    process::exit(0);
+                }
+
+                maps = Box::new(Maps::load(Path::new(&args[i])));
+                i += 1;
+            } else {
+                design.push(arg.clone());
+            }
+        }
+
+        (altitude, mach, throttle, maps, design)
+    }
+
+    /// Reads the `--requirements`, `--bounds`, `--bound` and `--threads` flags from anywhere in the arguments.
+    ///
+    /// Returns the requirements, bounds and the remaining arguments.
+    fn parse_limits(args: Vec<String>) -> (Requirements, Bounds, Vec<String>) {
+        let mut requirements = Requirements::default();
+        let mut bounds = Bounds::default();
+        let mut rest = Vec::new();
+
+        let mut i = 0;
+        while i < args.len() {
+            if args[i] == "--requirements" {
+                if i + 1 == args.len() {
+                    println!("[FATAL] Please specify a requirements file");
+                    process::exit(0);
+                }
+
+                requirements = Requirements::load(Path::new(&args[i + 1]));
+                i += 2;
+            } else if args[i] == "--bounds" {
+                if i + 1 == args.len() {
+                    println!("[FATAL] Please specify a bounds file");
+                    process::exit(0);
+                }
+
+                bounds.load(Path::new(&args[i + 1]));
+                i += 2;
+            } else if args[i] == "--bound" {
+                if i + 1 == args.len() {
+                    println!("[FATAL] Please specify a bound");
+                    process::exit(0);
+                }
+
+                bounds.set(&args[i + 1]);
+                i += 2;
+            } else if args[i] == "--threads" {
+                match args.get(i + 1).map(|s| str::parse::<usize>(s)) {
+                    Some (Ok (threads)) if threads > 0 => parallel::set_threads(threads),
+                    _ => {
+                        println!("[FATAL] Please specify a positive number of threads");
+                        process::exit(0);
+                    },
+                }
+                i += 2;
+            } else {
+                rest.push(args[i].clone());
+                i += 1;
+            }
+        }
+
+        (requirements, bounds, rest)
+    }
+
+    /// Reads the mission flags following a subcommand, starting from argument `i`.
+    ///
+    /// Returns the mission, maps and the remaining flags.
+    fn parse_mission(args: &[String], mut i: usize) -> (Mission, Box<Maps>, Vec<String>) {
+        let mut mission = Mission::default();
+        let mut maps = Box::<Maps>::default();
+        let mut rest = Vec::new();
+
+        while i < args.len() {
+            let arg = &args[i];
+            i += 1;
+
+            if arg == "--range" {
+                mission.range = 1000.0 * Self::parse_value(args, i);
+                i += 1;
+            } else if arg == "--takeoff-mass" {
+                mission.takeoff_mass = Self::parse_value(args, i);
+                i += 1;
+            } else if arg == "--lift-to-drag" {
+                mission.lift_to_drag = Self::parse_value(args, i);
+                i += 1;
+            } else if arg == "--altitude" {
+                mission.cruise_altitude = Self::parse_value(args, i);
+                i += 1;
+            } else if arg == "--mach" {
+                mission.cruise_mach = Self::parse_value(args, i);
+                i += 1;
+            } else if arg == "--maps" {
+                if i == args.len() {
+                    println!("[FATAL] Please specify a map directory");
+                    process::exit(0);
+                }
+
+                maps = Box::new(Maps::load(Path::new(&args[i])));
+                i += 1;
+            } else {
+                rest.push(arg.clone());
+            }
+        }
+
+        (mission, maps, rest)
+    }
+
+    /// Reads argument `i` as a numeric value.
+    fn parse_value(args: &[String], i: usize) -> f64 {
+        if i >= args.len() {
+            println!("[FATAL] Please specify a value for {}", &args[i - 1]);
+            process::exit(0);
+        }
+
+        match str::parse::<f64>(&args[i]) {
+            Ok (f) => f,
+            Err (_) => {
+                println!("[FATAL] Could not parse {} as numeric value", &args[i]);
+                process::exit(0);
+            },
+        }
+    }
+
+    /// Reads the flags following a subcommand, starting from argument `i`.
+    ///
+    /// Fixed values and the base design are validated against `bounds`.
+    fn parse_flags(args: &[String], mut i: usize, bounds: &Bounds) -> (Variables, Box<dyn Engine>) {
+        // Initialize variables, reading the base design first so that other flags apply on top of it
+        let flags = &args[i.min(args.len())..];
+        let mut allvars = match flags.iter().position(|arg| arg == "--design") {
+            Some (j) => match flags.get(j + 1) {
+                Some (file) => {
+                    let design = Variables::load(Path::new(file));
+                    if let Err (error) = bounds.check_design(design) {
+                        println!("[FATAL] In design {}: {}", file, error);
+                        process::exit(0);
+                    }
+                    design
+                },
+                None => {
+                    println!("[FATAL] Please specify a design file");
+                    process::exit(0);
+                },
+            },
+            None if flags.windows(2).any(|pair| pair[0] == "--spools" && pair[1] == "3") => VANILLA_PLUS_THREE_SPOOL,
+            None => VANILLA_PLUS,
+        };
+        let mut name = "turbofan".to_string();
+        let mut exhaust = Exhaust::Separate;
+        let mut afterburner = false;
+        let mut spools = Spools::Two;
+        let mut booster = true;
+        let mut geared = false;
+        let mut gas = GasModel::Perfect;
+        let mut fuel = Fuel::default();
+        let mut polytropic = Polytropic::default();
+        let mut cooling = Cooling::None;
+        let mut bleed_station = BleedStation::Hpc;
+
+        while i < args.len() {
+            let arg = &args[i];
+            i += 1;
+
+            if arg == "--fix" {
+                // Stop when you find another flag
+                while i < args.len() && !args[i].starts_with("--") {
+                    let option: VarSelector = args[i].clone().into();
+
+                    if args.len() == i + 1 {
+                        println!("[FATAL] Please specify a value for {}", &args[i]);
+                        process::exit(0);
+                    }
+
+                    let value = match str::parse::<f64>(&args[i + 1]) {
+                        Ok (f) => f,
+                        Err (_) => {
+                            println!("[FATAL] Could not parse {} as numeric value", &args[i + 1]);
+                            process::exit(0);
+                        },
+                    };
+
+                    bounds.validate(option, value);
+                    *allvars.get_reference(option) = value;
+
+                    i += 2;
+                }
+            } else if arg == "--exhaust" {
+                if i == args.len() {
+                    println!("[FATAL] Please specify an exhaust arrangement");
+                    process::exit(0);
+                }
+
+                exhaust = args[i].clone().into();
+                i += 1;
+            } else if arg == "--afterburner" {
+                afterburner = true;
+            } else if arg == "--spools" {
+                if i == args.len() {
+                    println!("[FATAL] Please specify a number of spools");
+                    process::exit(0);
+                }
+
+                spools = args[i].clone().into();
+                i += 1;
+            } else if arg == "--no-booster" {
+                booster = false;
+            } else if arg == "--geared" {
+                geared = true;
+            } else if arg == "--polytropic" {
+                if i == args.len() || args[i].starts_with("--") {
+                    println!("[FATAL] Please specify one or more components");
+                    process::exit(0);
+                }
+
+                // Stop when you find another flag
+                while i < args.len() && !args[i].starts_with("--") {
+                    polytropic.set(&args[i]);
+                    i += 1;
+                }
+            } else if arg == "--cooling" {
+                if i == args.len() {
+                    println!("[FATAL] Please specify a cooling model");
+                    process::exit(0);
+                }
+
+                cooling = args[i].clone().into();
+                i += 1;
+            } else if arg == "--bleed-station" {
+                if i == args.len() {
+                    println!("[FATAL] Please specify a bleed station");
+                    process::exit(0);
+                }
+
+                bleed_station = args[i].clone().into();
+                i += 1;
+            } else if arg == "--gas" {
+                if i == args.len() {
+                    println!("[FATAL] Please specify a gas model");
+                    process::exit(0);
+                }
+
+                gas = args[i].clone().into();
+                i += 1;
+            } else if arg == "--fuel" {
+                if i == args.len() {
+                    println!("[FATAL] Please specify a fuel");
+                    process::exit(0);
+                }
+
+                // Later `--fix` values override the heating value and specific heat of the fuel
+                fuel = args[i].clone().into();
+                allvars.fuel_delta_h = fuel.delta_h;
+                allvars.fuel_cp = fuel.cp;
+                i += 1;
+            } else if arg == "--engine" {
+                if i == args.len() {
+                    println!("[FATAL] Please specify an engine");
+                    process::exit(0);
+                }
+
+                name = args[i].clone();
+                i += 1;
+            } else if arg == "--design" {
+                i += 1;
+            } else {
+                println!("[ERROR] Unrecognized flag {}.  Type `jetlab` for help.", arg);
+            }
+        }
+
+        if let (BleedStation::Ipc, Spools::Two) = (bleed_station, spools) {
+            println!("[FATAL] Bleed station 26 is the IPC exit, which needs `--spools 3`");
+            process::exit(0);
+        }
+
+        // Flags that the selected engine would ignore
+        for flag in flags.iter().map(String::as_str) {
+            let applies = match name.as_str() {
+                "turbofan" => true,
+                "turbojet" => !TURBOFAN_FLAGS.contains(&flag),
+                _ => !TURBOFAN_FLAGS.contains(&flag) && flag != "--afterburner",
+            };
+            if !applies {
+                println!("[ERROR] Flag {} does not apply to the {}.  Type `jetlab` for help.", flag, name);
+            }
+        }
+
+        let engine: Box<dyn Engine> = match name.as_str() {
+            "turbofan" => Box::new(Turbofan {
+                exhaust,
+                afterburner,
+                spools,
+                booster,
+                geared,
+                gas,
+                fuel,
+                polytropic,
+                cooling,
+                bleed_station,
+            }),
+            "turbojet" => Box::new(Turbojet {
+                afterburner,
+                gas,
+                fuel,
+            }),
+            "turboprop" => Box::new(Turboprop {
+                gas,
+                fuel,
+            }),
+            "turboshaft" => Box::new(Turboshaft {
+                gas,
+                fuel,
+            }),
+            "ramjet" => Box::new(Ramjet {
+                gas,
+                fuel,
+            }),
+            _ => {
+                println!("[FATAL] Invalid engine {}.  Type `jetlab` for help.", &name);
+                process::exit(0);
+            },
+        };
+
+        (allvars, engine)
+    }
+
+    /// Ensures that a flight Mach number is given to engines that require one.
+    fn check_flight_mach(allvars: &Variables, engine: &dyn Engine) {
+        if engine.needs_flight_mach() && allvars.flight_mach_number <= 0.0 {
+            println!("[FATAL] The {} requires a flight Mach number.  Use `--fix flight_mach_number [VALUE]`.", engine.name().to_lowercase());
+            process::exit(0);
+        }
+    }
+
+    /// Ensures that an engine has an off-design model before it is run off-design.
+    fn check_off_design(engine: &dyn Engine) {
+        if let Err (error) = engine.check_off_design() {
+            println!("[FATAL] {}", error);
+            process::exit(0);
+        }
+    }
+
     /// Provides a help menu to the user.
     pub fn help() -> ! {
         println!("JETLAB");
@@ -157,6 +918,87 @@ impl Cli {
         println!("\t$ jetlab plot [VARIABLE] [LOWER_BOUND] [UPPER_BOUND] --fix [VARIABLE] [VALUE]");
         println!("\t$ jetlab optimize [VARIABLE]");
         println!("\t$ jetlab optimize [VARIABLE] --fix [VARIABLE] [VALUE]");
+        println!("\t$ jetlab optimize --objective fuel --range [KM]");
+        println!("\t$ jetlab optimize --objective weight");
+        println!("\t$ jetlab optimize --objective noise --noise-weight [WEIGHT]");
+        println!("\t$ jetlab analyze");
+        println!("\t$ jetlab analyze --engine [ENGINE] --fix [VARIABLE] [VALUE]");
+        println!("\t$ jetlab offdesign --altitude [M] --mach [MACH] --throttle [t4|fuel] [VALUE]");
+        println!("\t$ jetlab throttle [t4|fuel] [LOWER_BOUND] [UPPER_BOUND] --altitude [M] --mach [MACH]");
+        println!("\t$ jetlab installed --altitude [M] --mach [MACH] --throttle [t4|fuel] [VALUE]");
+        println!("\t$ jetlab mission --range [KM] --altitude [M] --mach [MACH]");
+        println!("\t$ jetlab uncertainty [SAMPLES] --vary [VARIABLE]=[DISTRIBUTION] ... --seed [SEED]");
+        println!("\t$ jetlab sensitivity [SAMPLES] --vary [VARIABLE] [VARIABLE]=[MIN]..[MAX] ... --seed [SEED]");
+        println!("\t$ jetlab doe [factorial|lhs|sobol] [SAMPLES] --vary [VARIABLE] [VARIABLE]=[MIN]..[MAX] ... --output [FILE]");
+        println!("\t$ jetlab batch [FILE] --design [FILE] --output [FILE]");
+
+        println!("\nOptions:");
+        println!("\t--fix [VARIABLE] [VALUE] ...\tFix one or more variables");
+        println!("\t--engine [ENGINE]\t\tSelect the engine architecture");
+        println!("\t--design [FILE]\t\t\tRead the base design from a `.jetlab` file instead of Vanilla Plus");
+        println!("\t--requirements [FILE]\t\tRead the thrust requirement, engine count and design limits from a file");
+        println!("\t--bound [VARIABLE]=[MIN]..[MAX]\tBound a variable for optimization and input validation");
+        println!("\t--bounds [FILE]\t\t\tRead variable bounds from a file, one `variable=min..max` per line");
+        println!("\t--threads [THREADS]\t\tEvaluate sweeps, samples and gradients on this many threads (default all cores)");
+        println!("\t--exhaust [separate|mixed]\tSelect separate or mixed exhaust streams (turbofan)");
+        println!("\t--afterburner\t\t\tLight the afterburner (turbofan, turbojet)");
+        println!("\t--spools [2|3]\t\t\tSelect a two- or three-spool turbofan, starting three spools from Vanilla Plus with a 3.0 IPC and 7.5 HPC");
+        println!("\t--no-booster\t\t\tRemove the booster (LPC) from the turbofan low spool");
+        println!("\t--geared\t\t\tDrive the turbofan fan through a reduction gearbox");
+        println!("\t--gas [perfect|nasa]\t\tSelect constant or temperature-dependent gas properties");
+        println!("\t--fuel [FUEL]\t\t\tSelect the fuel, setting its heating value and specific heat (default jet-a)");
+        println!("\t--polytropic [COMPONENT] ...\tRead efficiencies as polytropic (fan, lpc, ipc, hpc, hpt, ipt, lpt or all; turbofan)");
+        println!("\t--bleed-station [25|26|3]\tTake customer bleed air from the LPC, IPC or HPC exit (turbofan)");
+        println!("\t--cooling [none|fixed|metal]\tSize HPT cooling air by fixed fractions or blade metal temperature (turbofan)");
+
+        println!("\nOff-Design Options:");
+        println!("\t--altitude [M]\t\t\tSet the ISA altitude (default 0)");
+        println!("\t--mach [MACH]\t\t\tSet the flight Mach number (default 0)");
+        println!("\t--throttle [t4|fuel] [VALUE]\tSet the combustor exit temperature (K) or fuel flow (kg/s)");
+        println!("\t--maps [DIRECTORY]\t\tRead fan.map, compressor.map and turbine.map instead of the generic maps");
+
+        println!("\nMission Options:");
+        println!("\t--range [KM]\t\t\tSet the mission range (default 3000)");
+        println!("\t--altitude [M]\t\t\tSet the cruise altitude (default 10668)");
+        println!("\t--mach [MACH]\t\t\tSet the cruise Mach number (default 0.78)");
+        println!("\t--takeoff-mass [KG]\t\tSet the aircraft takeoff mass (default 75000)");
+        println!("\t--lift-to-drag [L/D]\t\tSet the aircraft lift-to-drag ratio (default 17)");
+        println!("\t--objective [sfc|fuel|weight|noise]\tMinimize design-point SFC, mission block fuel, dry weight or jet noise (optimize)");
+        println!("\t--noise-weight [WEIGHT]\t\tWeight jet noise against SFC from 0 to 1 for a Pareto front (default 1)");
+        println!("\t--maps [DIRECTORY]\t\tRead fan.map, compressor.map and turbine.map instead of the generic maps");
+
+        println!("\nUncertainty and Sensitivity Options:");
+        println!("\t--vary [VARIABLE]=[DISTRIBUTION] ...\tSample one or more variables (uncertainty; default 1000 samples)");
+        println!("\t--vary [VARIABLE] ...\t\tSample one or more variables between their bounds (sensitivity; default 1000 samples)");
+        println!("\t--seed [SEED]\t\t\tSeed the random number generator (default 0)");
+
+        println!("\nDesign of Experiments Options:");
+        println!("\t[SAMPLES]\t\t\tSet the number of samples (default 100), or the levels per variable of a factorial design (default 5)");
+        println!("\t--vary [VARIABLE] ...\t\tSample one or more variables between their bounds");
+        println!("\t--seed [SEED]\t\t\tSeed the Latin hypercube permutations (default 0)");
+        println!("\t--output [FILE]\t\t\tWrite the inputs, outputs and requirement flags of each sample to a CSV file (default doe.csv)");
+
+        println!("\nBatch Options:");
+        println!("\t[FILE]\t\t\t\tRead one design per row from a CSV file, with a header of variable names");
+        println!("\t--design [FILE]\t\t\tRead the values of missing columns and empty cells from a `.jetlab` file");
+        println!("\t--output [FILE]\t\t\tWrite the inputs, outputs, station data and requirement flags of each design to a CSV file (default batch.csv)");
+
+        println!("\nDistributions:");
+        println!("\tnormal:[MEAN],[STD_DEV]");
+        println!("\tuniform:[MIN],[MAX]");
+        println!("\ttriangular:[MIN],[MODE],[MAX]");
+
+        println!("\nEngines:");
+        println!("\tturbofan");
+        println!("\tturbojet");
+        println!("\tturboprop");
+        println!("\tturboshaft");
+        println!("\tramjet (requires flight_mach_number)");
+
+        println!("\nFuels:");
+        for fuel in FUELS.iter() {
+            println!("\t{}", fuel.name);
+        }
 
         println!("\nFree Variables:");
         println!("\tinlet_mach_number");
@@ -165,8 +1007,12 @@ impl Cli {
         println!("\tfan_pressure_ratio");
         println!("\tfan_efficiency");
         println!("\tfan_bypass");
+        println!("\tgear_ratio");
+        println!("\tgearbox_efficiency");
         println!("\tlpc_pressure_ratio");
         println!("\tlpc_efficiency");
+        println!("\tipc_pressure_ratio");
+        println!("\tipc_efficiency");
         println!("\thpc_pressure_ratio");
         println!("\thpc_efficiency");
         println!("\thpc_discharge_temp");
@@ -174,10 +1020,30 @@ impl Cli {
         println!("\tcombustor_efficiency");
         println!("\thpt_inlet_temp");
         println!("\thpt_efficiency");
+        println!("\tipt_efficiency");
         println!("\tlpt_efficiency");
         println!("\tbypass_pressure_recovery");
         println!("\tfuel_delta_h");
         println!("\tfuel_cp");
+        println!("\tmixer_mach_number");
+        println!("\tmixer_efficiency");
+        println!("\tafterburner_exit_temp");
+        println!("\tafterburner_pressure_recovery");
+        println!("\tafterburner_efficiency");
+        println!("\tpower_turbine_efficiency");
+        println!("\tnozzle_pressure_ratio");
+        println!("\tpropeller_efficiency");
+        println!("\tpropeller_diameter");
+        println!("\tflight_mach_number");
+        println!("\tburner_mach_number");
+        println!("\tburner_exit_temp");
+        println!("\tngv_cooling_fraction");
+        println!("\trotor_cooling_fraction");
+        println!("\tblade_metal_temp");
+        println!("\tcooling_flow_factor");
+        println!("\tcustomer_bleed_fraction");
+        println!("\thp_power_offtake");
+        println!("\tlp_power_offtake");
         process::exit(0);
     }
 }
\ No newline at end of file
@@ -5,20 +5,29 @@ use crate::Variables;
 /// Plane Vanilla Plus.
 pub const VANILLA_PLUS: Variables = include!("vanilla-plus.jetlab");
 
+/// Plane Vanilla Plus with three spools.
+pub const VANILLA_PLUS_THREE_SPOOL: Variables = include!("vanilla-plus-3.jetlab");
+
 /// Optimization rate.
 pub const OPTIMIZATION_RATE: f64 = 0.1;
 
-/// Maximum allowable overall pressure ratio.
+/// Number of times the optimizer halves a rejected step before it stops.
+pub const OPTIMIZATION_BACKTRACKS: usize = 10;
+
+/// Default overall pressure ratio held by the optimizer.
 pub const OPR: f64 = 47.0;
 
-/// Number of aircraft engines.
+/// Default number of aircraft engines.
 pub const ENGINE_COUNT: usize = 2;
 
-/// Thrust safety margin.
+/// Default thrust safety margin.
 pub const THRUST_SAFETY_MARGIN: f64 = 0.01;
 
-/// Minimum allowable thrust.
-pub const MIN_THRUST: f64 = 2.0 * 80067.989 * (1.0 + THRUST_SAFETY_MARGIN) / ENGINE_COUNT as f64;
+/// Default total takeoff thrust required of all engines (in N).
+pub const REQUIRED_THRUST: f64 = 2.0 * 80067.989;
+
+/// Default minimum shaft power (in kW) for shaft power engines.
+pub const MIN_SHAFT_POWER: f64 = 10_000.0;
 
 /// Ratio of specific heats for air.
 pub const GAMMA: f64 = 1.400;
@@ -39,9 +48,15 @@ pub const P0: f64 = 101325.353;
 pub const CP_AIR: f64 = 1004.50;
 
 /// Pi.
-pub const PI: f64 = 3.141549265358979323846264;
+pub const PI: f64 = 3.141549265358979;
+
+/// Pounds-force per newton.
+pub const LBF_PER_N: f64 = 0.224809;
+
+/// Pounds-mass per kilogram.
+pub const LBM_PER_KG: f64 = 2.20462262185;
 
-/// Limitations on gradient ascent optimizations.
+/// Default bounds on the free variables.
 pub const INLET_MACH_NUMBER_MIN: f64                = 0.200;
 pub const INLET_MACH_NUMBER_MAX: f64                = 0.635;
 pub const INLET_DIAMETER_MIN: f64                   = 1.2192;
@@ -52,9 +67,25 @@ pub const FAN_BYPASS_MIN: f64                       = 1.0;
 pub const FAN_BYPASS_MAX: f64                       = 11.0;
 pub const LPC_PRESSURE_RATIO_MIN: f64               = 1.0;
 pub const LPC_PRESSURE_RATIO_MAX: f64               = 3.0;
+pub const IPC_PRESSURE_RATIO_MIN: f64               = 1.0;
+pub const IPC_PRESSURE_RATIO_MAX: f64               = 8.0;
 pub const HPC_PRESSURE_RATIO_MIN: f64               = 1.0;
 pub const HPC_PRESSURE_RATIO_MAX: f64               = 22.5;
 pub const HPC_DISCHARGE_TEMP_MIN: f64               = 0.0;
 pub const HPC_DISCHARGE_TEMP_MAX: f64               = 1172.039;
 pub const HPT_INLET_TEMP_MIN: f64                   = 0.0;
-pub const HPT_INLET_TEMP_MAX: f64                   = 1699.817;
\ No newline at end of file
+pub const HPT_INLET_TEMP_MAX: f64                   = 1699.817;
+pub const BURNER_EXIT_TEMP_MIN: f64                 = 0.0;
+pub const BURNER_EXIT_TEMP_MAX: f64                 = 2400.0;
+pub const EFFICIENCY_MIN: f64                       = 0.0;
+pub const EFFICIENCY_MAX: f64                       = 1.0;
+pub const PRESSURE_RECOVERY_MIN: f64                = 0.0;
+pub const PRESSURE_RECOVERY_MAX: f64                = 1.0;
+pub const FUEL_DELTA_H_MIN: f64                     = 1.0E+6;
+pub const FUEL_DELTA_H_MAX: f64                     = 1.5E+8;
+pub const FUEL_CP_MIN: f64                          = 500.0;
+pub const FUEL_CP_MAX: f64                          = 5000.0;
+pub const BLEED_FRACTION_MIN: f64                   = 0.0;
+pub const BLEED_FRACTION_MAX: f64                   = 0.5;
+pub const POWER_OFFTAKE_MIN: f64                    = 0.0;
+pub const POWER_OFFTAKE_MAX: f64                    = f64::INFINITY;
\ No newline at end of file
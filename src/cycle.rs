@@ -0,0 +1,194 @@
+//! Holds the results of an engine cycle analysis.
+
+use std::fmt::{
+    Display,
+    Formatter,
+    Result,
+};
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the stagnation state at one engine station.
+pub struct Station {
+    /// Station name (e.g. "25" for the LPC exit)
+    pub name: &'static str,
+
+    /// Total temperature (in K)
+    pub total_temp: f64,
+
+    /// Total pressure (in Pa)
+    pub total_pressure: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the efficiencies of one turbomachine.
+pub struct Efficiency {
+    /// Component name (e.g. "HPC")
+    pub name: &'static str,
+
+    /// Isentropic (adiabatic) efficiency
+    pub isentropic: f64,
+
+    /// Polytropic (small-stage) efficiency
+    pub polytropic: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the operating point of one turbomachine on its map.
+pub struct MapPoint {
+    /// Component name (e.g. "HPC")
+    pub name: &'static str,
+
+    /// Corrected speed relative to the design point
+    pub speed: f64,
+
+    /// Beta line
+    pub beta: f64,
+
+    /// Corrected mass flow, referred to the design inlet conditions (in kg/s)
+    pub flow: f64,
+
+    /// Total pressure ratio, or total expansion ratio of a turbine
+    pub ratio: f64,
+
+    /// Isentropic efficiency
+    pub efficiency: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the fully expanded exit conditions of one nozzle.
+pub struct Jet {
+    /// Nozzle name (e.g. "9" for the core nozzle)
+    pub name: &'static str,
+
+    /// Mass flow rate (in kg/s)
+    pub mass_flow: f64,
+
+    /// Exit velocity (in m/s)
+    pub velocity: f64,
+
+    /// Exit static density (in kg/m3)
+    pub density: f64,
+
+    /// Fully expanded exit area (in m2)
+    pub area: f64,
+}
+
+#[derive(Clone, Debug, Default)]
+/// Holds the performance and station data of an engine cycle.
+pub struct Cycle {
+    /// Net thrust, or equivalent thrust for shaft power engines (in N)
+    pub thrust: f64,
+
+    /// Specific fuel consumption (in kg/N-hr)
+    pub sfc: f64,
+
+    /// Inlet mass flow rate (in kg/s)
+    pub mass_flow: f64,
+
+    /// Total fuel flow rate (in kg/s)
+    pub fuel_flow: f64,
+
+    /// Shaft power delivered to the load (in W)
+    pub shaft_power: f64,
+
+    /// Highest fuel-air ratio leaving any burner
+    pub fuel_air_ratio: f64,
+
+    /// Stagnation states at each station, from inlet to nozzle
+    pub stations: Vec<Station>,
+
+    /// Isentropic and polytropic efficiencies of each turbomachine
+    pub efficiencies: Vec<Efficiency>,
+
+    /// Map operating points of each turbomachine, for off-design cycles
+    pub map_points: Vec<MapPoint>,
+
+    /// Exit conditions of each nozzle
+    pub jets: Vec<Jet>,
+}
+
+impl Cycle {
+    /// Adds a station to this cycle.
+    pub fn station(&mut self, name: &'static str, total_temp: f64, total_pressure: f64) {
+        self.stations.push(Station {
+            name,
+            total_temp,
+            total_pressure,
+        });
+    }
+
+    /// Adds the efficiencies of a turbomachine to this cycle.
+    pub fn efficiency(&mut self, name: &'static str, isentropic: f64, polytropic: f64) {
+        self.efficiencies.push(Efficiency {
+            name,
+            isentropic,
+            polytropic,
+        });
+    }
+
+    /// Adds the map operating point of a turbomachine to this cycle.
+    pub fn map_point(&mut self, name: &'static str, speed: f64, beta: f64, flow: f64, ratio: f64, efficiency: f64) {
+        self.map_points.push(MapPoint {
+            name,
+            speed,
+            beta,
+            flow,
+            ratio,
+            efficiency,
+        });
+    }
+
+    /// Adds the exit conditions of a nozzle to this cycle.
+    pub fn jet(&mut self, name: &'static str, mass_flow: f64, velocity: f64, density: f64) {
+        self.jets.push(Jet {
+            name,
+            mass_flow,
+            velocity,
+            density,
+            area: mass_flow / (density * velocity),
+        });
+    }
+}
+
+impl Display for Cycle {
+    /// Format this data structure.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut output = String::new();
+
+        output.push_str(&format!("Mass flow rate: {:.6} kg/s\n", self.mass_flow));
+        output.push_str(&format!("Fuel flow rate: {:.6} kg/s\n", self.fuel_flow));
+        output.push_str(&format!("Burner fuel-air ratio: {:.6}\n", self.fuel_air_ratio));
+        if self.shaft_power > 0.0 {
+            output.push_str(&format!("Shaft power: {:.6} kW\n", self.shaft_power/1000.0));
+            output.push_str(&format!("PSFC: {:.6} kg/kW-hr\n", self.fuel_flow*3600.0/(self.shaft_power/1000.0)));
+        }
+
+        output.push_str(&format!("\n{:<10}{:>16}{:>16}\n", "Station", "Tt (K)", "Pt (kPa)"));
+        for station in &self.stations {
+            output.push_str(&format!("{:<10}{:>16.3}{:>16.3}\n", station.name, station.total_temp, station.total_pressure/1000.0));
+        }
+
+        if !self.efficiencies.is_empty() {
+            output.push_str(&format!("\n{:<10}{:>16}{:>16}\n", "Component", "Isentropic", "Polytropic"));
+            for efficiency in &self.efficiencies {
+                output.push_str(&format!("{:<10}{:>16.4}{:>16.4}\n", efficiency.name, efficiency.isentropic, efficiency.polytropic));
+            }
+        }
+
+        if !self.map_points.is_empty() {
+            output.push_str(&format!("\n{:<10}{:>12}{:>12}{:>16}{:>12}{:>12}\n", "Component", "Speed", "Beta", "Flow (kg/s)", "Ratio", "Efficiency"));
+            for point in &self.map_points {
+                output.push_str(&format!("{:<10}{:>12.4}{:>12.4}{:>16.3}{:>12.4}{:>12.4}\n", point.name, point.speed, point.beta, point.flow, point.ratio, point.efficiency));
+            }
+        }
+
+        if !self.jets.is_empty() {
+            output.push_str(&format!("\n{:<10}{:>16}{:>16}{:>16}{:>16}\n", "Nozzle", "Flow (kg/s)", "V (m/s)", "Rho (kg/m3)", "Area (m2)"));
+            for jet in &self.jets {
+                output.push_str(&format!("{:<10}{:>16.3}{:>16.3}{:>16.4}{:>16.4}\n", jet.name, jet.mass_flow, jet.velocity, jet.density, jet.area));
+            }
+        }
+
+        write!(f, "{}", output)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,236 @@
+//! Generates designs of experiments over selected variables.
+//!
+//! Each selected variable is sampled between its bounds.  Three designs are
+//! available:
+//!
+//! - `factorial` places a grid of `n` evenly spaced levels on each variable,
+//!   giving `n^k` samples for `k` variables;
+//! - `lhs` (Latin hypercube) divides each variable into `n` strata and places
+//!   one sample in each, pairing the strata by random permutation;
+//! - `sobol` takes the first `n` points of the Sobol low-discrepancy sequence,
+//!   with the direction numbers of Joe and Kuo, for up to 16 variables.
+
+use std::process;
+
+use crate::{
+    Bounds,
+    Random,
+    Variables,
+    VarSelector,
+};
+
+/// Number of bits in each Sobol coordinate.
+const SOBOL_BITS: usize = 32;
+
+/// Degree, coefficients and initial direction numbers of the primitive polynomials for Sobol dimensions 2 to 16.
+const SOBOL_DIRECTIONS: [(usize, u32, &[u32]); 15] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Selects how samples are placed in the design space.
+pub enum Design {
+    /// Full factorial grid
+    Factorial,
+
+    /// Latin hypercube
+    LatinHypercube,
+
+    /// Sobol sequence
+    Sobol,
+}
+
+impl From<String> for Design {
+    fn from(string: String) -> Self {
+        match string.as_str() {
+            "factorial" => Design::Factorial,
+            "lhs" => Design::LatinHypercube,
+            "sobol" => Design::Sobol,
+            _ => {
+                println!("[FATAL] Invalid design {}.  Use `factorial`, `lhs` or `sobol`.", &string);
+                process::exit(0);
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Holds the variables and sampling settings of a design of experiments.
+pub struct Experiment {
+    /// Placement of samples
+    pub design: Design,
+
+    /// Variables sampled between their bounds
+    pub inputs: Vec<VarSelector>,
+
+    /// Number of samples, or number of levels per variable for a factorial design
+    pub samples: usize,
+
+    /// Seed of the random number generator, for Latin hypercube designs
+    pub seed: u64,
+}
+
+impl Experiment {
+    /// Generates the samples in the unit hypercube, one row per sample.
+    pub fn unit_points(&self) -> Vec<Vec<f64>> {
+        let k = self.inputs.len();
+
+        match self.design {
+            Design::Factorial => {
+                let levels = self.samples;
+                let count = levels.checked_pow(k as u32).unwrap_or(usize::MAX);
+                if count > 1_000_000 {
+                    println!("[FATAL] A factorial design of {} levels on {} variables is too large", levels, k);
+                    process::exit(0);
+                }
+
+                (0..count).map(|index| {
+                    let mut index = index;
+                    (0..k).map(|_| {
+                        let level = index % levels;
+                        index /= levels;
+                        level as f64 / (levels - 1) as f64
+                    }).collect()
+                }).collect()
+            },
+            Design::LatinHypercube => {
+                let n = self.samples;
+                let mut random = Random::new(self.seed);
+                let mut points = vec![vec![0.0; k]; n];
+
+                for j in 0..k {
+                    // Fisher-Yates shuffle of the strata
+                    let mut strata = (0..n).collect::<Vec<usize>>();
+                    for i in (1..n).rev() {
+                        strata.swap(i, (random.next_u64() % (i as u64 + 1)) as usize);
+                    }
+                    for (point, stratum) in points.iter_mut().zip(strata) {
+                        point[j] = (stratum as f64 + random.uniform()) / n as f64;
+                    }
+                }
+
+                points
+            },
+            Design::Sobol => sobol(self.samples, k),
+        }
+    }
+
+    /// Generates the samples around a design, scaling each variable between its bounds.
+    ///
+    /// Returns an error if a variable does not have finite bounds.
+    pub fn points(&self, variables: Variables, bounds: &Bounds) -> Result<Vec<Variables>, String> {
+        let ranges = bounds.ranges(&self.inputs)?;
+
+        Ok (self.unit_points().iter().map(|point| {
+            let mut sample = variables;
+            for ((var, &(min, max)), &x) in self.inputs.iter().zip(&ranges).zip(point) {
+                *sample.get_reference(*var) = min + x * (max - min);
+            }
+            sample
+        }).collect())
+    }
+}
+
+/// Generates the first `n` points of the Sobol sequence in `k` dimensions.
+fn sobol(n: usize, k: usize) -> Vec<Vec<f64>> {
+    if k > SOBOL_DIRECTIONS.len() + 1 {
+        println!("[FATAL] Sobol designs support at most {} variables", SOBOL_DIRECTIONS.len() + 1);
+        process::exit(0);
+    }
+
+    // Direction numbers of each dimension, scaled to the full word
+    let directions = (0..k).map(|j| {
+        let mut v = [0u32; SOBOL_BITS];
+        if j == 0 {
+            for (i, v) in v.iter_mut().enumerate() {
+                *v = 1 << (SOBOL_BITS - 1 - i);
+            }
+        } else {
+            let (s, a, m) = SOBOL_DIRECTIONS[j - 1];
+            for i in 0..SOBOL_BITS {
+                v[i] = if i < s {
+                    m[i] << (SOBOL_BITS - 1 - i)
+                } else {
+                    let mut value = v[i - s] ^ (v[i - s] >> s);
+                    for l in 1..s {
+                        value ^= ((a >> (s - 1 - l)) & 1) * v[i - l];
+                    }
+                    value
+                };
+            }
+        }
+        v
+    }).collect::<Vec<[u32; SOBOL_BITS]>>();
+
+    // Gray code construction, flipping the direction number of the lowest zero bit
+    let mut x = vec![0u32; k];
+    let mut points = Vec::with_capacity(n);
+    for i in 0..n {
+        points.push(x.iter().map(|&x| x as f64 / (1u64 << SOBOL_BITS) as f64).collect());
+
+        let c = (!i).trailing_zeros() as usize;
+        if c < SOBOL_BITS {
+            for (x, v) in x.iter_mut().zip(&directions) {
+                *x ^= v[c];
+            }
+        }
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn experiment(design: Design, samples: usize, k: usize) -> Experiment {
+        Experiment {
+            design,
+            inputs: vec![VarSelector::FanBypass; k],
+            samples,
+            seed: 0,
+        }
+    }
+
+    #[test]
+    fn sobol_first_points() {
+        let points = sobol(4, 2);
+
+        assert_eq!(points.iter().map(|p| p[0]).collect::<Vec<f64>>(), [0.0, 0.5, 0.75, 0.25]);
+        assert_eq!(points.iter().map(|p| p[1]).collect::<Vec<f64>>(), [0.0, 0.5, 0.25, 0.75]);
+    }
+
+    #[test]
+    fn latin_hypercube_fills_each_stratum() {
+        let n = 10;
+        let points = experiment(Design::LatinHypercube, n, 3).unit_points();
+
+        for j in 0..3 {
+            let mut strata = points.iter().map(|p| (p[j] * n as f64) as usize).collect::<Vec<usize>>();
+            strata.sort();
+            assert_eq!(strata, (0..n).collect::<Vec<usize>>());
+        }
+    }
+
+    #[test]
+    fn factorial_grid() {
+        let points = experiment(Design::Factorial, 3, 2).unit_points();
+
+        assert_eq!(points.len(), 9);
+        assert_eq!(points[5], [1.0, 0.5]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,103 @@
+//! Estimates engine emissions from combustor inlet conditions and fuel flow.
+//!
+//! NOx follows the P3-T3 correlation
+//!
+//! ```text
+//! EI_NOx = 32 (P3 / 2965 kPa)^0.4 exp((T3 - 826 K)/194 K + (6.29 - 100 h)/53.2)
+//! ```
+//!
+//! in g/kg of fuel, where `h` is the specific humidity of the inlet air.  CO2 and
+//! H2O follow from the composition of the fuel, so their emissions indices are
+//! constant for a given fuel.
+
+use std::fmt::{
+    self,
+    Display,
+    Formatter,
+};
+
+use crate::{
+    Cycle,
+    Fuel,
+};
+
+/// Reference combustor inlet pressure of the NOx correlation (in Pa).
+const NOX_REFERENCE_PRESSURE: f64 = 2965.0E+3;
+
+/// Reference combustor inlet temperature of the NOx correlation (in K).
+const NOX_REFERENCE_TEMP: f64 = 826.0;
+
+/// Specific humidity of the inlet air (in kg water/kg dry air).
+const SPECIFIC_HUMIDITY: f64 = 0.00634;
+
+/// Computes the NOx emissions index (in g/kg of fuel) at a combustor inlet total temperature (in K) and pressure (in Pa).
+pub fn nox_index(t_t3: f64, p_t3: f64) -> f64 {
+    32.0 * (p_t3 / NOX_REFERENCE_PRESSURE).powf(0.4)
+        * ((t_t3 - NOX_REFERENCE_TEMP)/194.0 + (6.29 - 100.0*SPECIFIC_HUMIDITY)/53.2).exp()
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the emissions of an engine at one operating point.
+pub struct Emissions {
+    /// NOx emissions index (in g/kg of fuel)
+    pub nox_index: f64,
+
+    /// NOx mass flow rate (in g/s)
+    pub nox: f64,
+
+    /// CO2 mass flow rate (in g/s)
+    pub co2: f64,
+
+    /// Net lifecycle CO2 mass flow rate (in g/s)
+    pub net_co2: f64,
+
+    /// H2O mass flow rate (in g/s)
+    pub h2o: f64,
+}
+
+impl Emissions {
+    /// Estimates the emissions of a cycle burning a given fuel from its combustor inlet (station 3) and fuel flow.
+    ///
+    /// Returns `None` if the cycle has no combustor inlet station.
+    pub fn new(cycle: &Cycle, fuel: Fuel) -> Option<Self> {
+        let station = cycle.stations.iter().find(|s| s.name == "3")?;
+        let nox_index = nox_index(station.total_temp, station.total_pressure);
+        let co2 = fuel.ei_co2() * cycle.fuel_flow;
+
+        Some (Self {
+            nox_index,
+            nox: nox_index * cycle.fuel_flow,
+            co2,
+            net_co2: fuel.lifecycle_co2 * co2,
+            h2o: fuel.ei_h2o() * cycle.fuel_flow,
+        })
+    }
+}
+
+impl Display for Emissions {
+    /// Format this data structure.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+
+        output.push_str(&format!("NOx emissions index: {:.3} g/kg\n", self.nox_index));
+        output.push_str(&format!("NOx: {:.3} g/s\n", self.nox));
+        output.push_str(&format!("CO2: {:.3} g/s\n", self.co2));
+        output.push_str(&format!("Net lifecycle CO2: {:.3} g/s\n", self.net_co2));
+        output.push_str(&format!("H2O: {:.3} g/s\n", self.h2o));
+
+        write!(f, "{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nox_index_matches_correlation() {
+        for (t_t3, p_t3, expected) in [(826.0, 2965.0E+3, 35.590), (700.0, 1500.0E+3, 14.154)] {
+            let ei = nox_index(t_t3, p_t3);
+            assert!((ei - expected).abs() <= 1.0E-3, "EI NOx at {} K and {} Pa: {} != {}", t_t3, p_t3, ei, expected);
+        }
+    }
+}
\ No newline at end of file
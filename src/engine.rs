@@ -0,0 +1,410 @@
+//! Abstracts over engine architectures.
+
+use crate::{
+    constants::*,
+    parallel,
+    Bounds,
+    plot,
+    plot_map,
+    plot_series,
+    Condition,
+    Cycle,
+    Emissions,
+    Fuel,
+    Maps,
+    Nacelle,
+    Noise,
+    Objective,
+    Requirements,
+    ScaledMap,
+    Size,
+    Throttle,
+    Variables,
+    VarSelector,
+};
+
+/// Variables optimized by gradient descent.
+const GRADIENT_VARIABLES: [VarSelector; 10] = [
+    VarSelector::InletMachNumber,
+    VarSelector::InletDiameter,
+    VarSelector::FanPressureRatio,
+    VarSelector::FanBypass,
+    VarSelector::LpcPressureRatio,
+    VarSelector::IpcPressureRatio,
+    VarSelector::HpcPressureRatio,
+    VarSelector::HpcDischargeTemp,
+    VarSelector::HptInletTemp,
+    VarSelector::BurnerExitTemp,
+];
+
+/// Describes an engine whose cycle can be analyzed, plotted and optimized.
+///
+/// Engines are shared between threads when batches are evaluated in parallel.
+pub trait Engine: Sync {
+    /// Computes the thermodynamic cycle of this engine.
+    fn cycle(&self, variables: Variables) -> Cycle;
+
+    /// Computes the overall pressure ratio of this engine.
+    fn overall_pressure_ratio(&self, variables: Variables) -> f64;
+
+    /// Names this engine.
+    fn name(&self) -> String;
+
+    /// Labels the thrust output of this engine.
+    fn thrust_label(&self) -> &'static str {
+        "Thrust (N)"
+    }
+
+    /// Labels the specific fuel consumption output of this engine.
+    fn sfc_label(&self) -> &'static str {
+        "SFC (kg/N-hr)"
+    }
+
+    /// Formats the thrust and SFC outputs of this engine, in SI and imperial units.
+    fn performance(&self, thrust: f64, sfc: f64) -> String {
+        let short = |label: &'static str| label.split(" (").next().unwrap_or(label);
+
+        let mut output = String::new();
+        output.push_str(&format!("{}: {:.6}\n", self.thrust_label(), thrust));
+        output.push_str(&format!("{}: {:.6}\n", self.sfc_label(), sfc));
+        output.push_str(&format!("{}: {:.6} lbf\n", short(self.thrust_label()), thrust*LBF_PER_N));
+        output.push_str(&format!("{}: {:.6} lbm/lbf-hr\n", short(self.sfc_label()), sfc*LBM_PER_KG/LBF_PER_N));
+
+        output
+    }
+
+    /// Indicates whether this engine requires a flight Mach number.
+    fn needs_flight_mach(&self) -> bool {
+        false
+    }
+
+    /// Gives the fuel burned by this engine.
+    fn fuel(&self) -> Fuel {
+        Fuel::default()
+    }
+
+    /// Gives the minimum allowable thrust of this engine under a set of requirements.
+    fn min_thrust(&self, requirements: &Requirements) -> f64 {
+        requirements.min_thrust()
+    }
+
+    /// Checks that this engine has an off-design model.
+    ///
+    /// Returns the reason if it has none.
+    fn check_off_design(&self) -> Result<(), String> {
+        Err (format!("The {} has no off-design model", self.name().to_lowercase()))
+    }
+
+    /// Computes the off-design cycle of this engine, sized by `variables`, at a flight condition and throttle setting.
+    ///
+    /// Returns `None` if the engine has no off-design model or cannot be matched at this condition.
+    fn off_design(&self, _variables: Variables, _maps: &Maps, _condition: Condition) -> Option<Cycle> {
+        None
+    }
+
+    /// Scales the HPC map of this engine to its design point, if the engine has one.
+    fn compressor_map(&self, _variables: Variables, _maps: &Maps) -> Option<ScaledMap> {
+        None
+    }
+
+    /// Estimates the dry weight and dimensions of this engine, if the engine has a weight model.
+    fn size(&self, variables: Variables) -> Option<Size> {
+        self.size_cycle(variables, &self.cycle(variables))
+    }
+
+    /// Estimates the dry weight and dimensions of this engine from its computed cycle.
+    fn size_cycle(&self, _variables: Variables, _cycle: &Cycle) -> Option<Size> {
+        None
+    }
+
+    /// Estimates the nacelle geometry of this engine, if the engine has a nacelle model.
+    fn nacelle(&self, _variables: Variables) -> Option<Nacelle> {
+        None
+    }
+
+    /// Computes the thrust and SFC of this engine.
+    fn analyze(&self, variables: Variables) -> (f64, f64) {
+        self.analyze_cycle(&self.cycle(variables))
+    }
+
+    /// Computes the thrust and SFC of this engine from its computed cycle.
+    fn analyze_cycle(&self, cycle: &Cycle) -> (f64, f64) {
+        (cycle.thrust, cycle.sfc)
+    }
+
+    /// Compute the partial derivative of the thrust at a given value with respect to a given variable.
+    fn delta_thrust(&self, variables: Variables, var: VarSelector) -> f64 {
+        let dx: f64 = 1.0E-6;
+
+        let mut variables_high = variables;
+        *variables_high.get_reference(var) += dx;
+
+        let mut variables_low = variables;
+        *variables_low.get_reference(var) -= dx;
+
+        (self.analyze(variables_high).0 - self.analyze(variables_low).0)/(2.0*dx)
+    }
+
+    /// Compute the partial derivative of an objective (SFC by default) at a given value with respect to a given variable.
+    fn delta_sfc(&self, variables: Variables, var: VarSelector, objective: &Objective) -> f64 {
+        let dx = 0.001;
+
+        let mut variables_high = variables;
+        *variables_high.get_reference(var) += dx;
+
+        let mut variables_low = variables;
+        *variables_low.get_reference(var) -= dx;
+
+        (objective.evaluate(self, variables_high).1 - objective.evaluate(self, variables_low).1)/(2.0*dx)
+    }
+
+    /// Compute the gradient of the thrust value with respect to all variables.
+    ///
+    /// Each partial derivative is given its own thread.
+    fn thrust_gradient(&self, variables: Variables) -> Variables {
+        let deltas = parallel::map_expensive(&GRADIENT_VARIABLES, |&var| self.delta_thrust(variables, var));
+
+        let mut gradient = variables.mult(0.0);
+        for (&var, delta) in GRADIENT_VARIABLES.iter().zip(deltas) {
+            *gradient.get_reference(var) = delta;
+        }
+
+        gradient
+    }
+
+    /// Compute the gradient of an objective with respect to all variables.
+    ///
+    /// Each partial derivative is given its own thread.
+    fn sfc_gradient(&self, variables: Variables, objective: &Objective) -> Variables {
+        let deltas = parallel::map_expensive(&GRADIENT_VARIABLES, |&var| self.delta_sfc(variables, var, objective));
+
+        let mut gradient = variables.mult(0.0);
+        for (&var, delta) in GRADIENT_VARIABLES.iter().zip(deltas) {
+            *gradient.get_reference(var) = delta;
+        }
+
+        gradient
+    }
+
+    /// Perform a gradient ascent optimization step for maximizing thrust.
+    fn step_thrust_optimization(&self, variables: Variables, bounds: &Bounds) -> Variables {
+        let step = self.thrust_gradient(variables).mult(OPTIMIZATION_RATE);
+        
+        bounds.clamp(variables + step)
+    }
+
+    /// Perform a gradient descent optimization step for minimizing an objective.
+    fn step_sfc_optimization(&self, mut variables: Variables, rate: f64, objective: &Objective, requirements: &Requirements, bounds: &Bounds) -> Variables {
+        let step = self.sfc_gradient(variables, objective).mult(rate);
+
+        // Engines without a compressor have no pressure ratio to hold
+        let opr = self.overall_pressure_ratio(variables);
+        if opr > 1.0 {
+            variables.hpc_pressure_ratio *= requirements.overall_pressure_ratio / opr;
+        }
+
+        // The step may raise the overall pressure ratio above its limit again
+        variables = variables + step.mult(-1.0);
+        let opr = self.overall_pressure_ratio(variables);
+        if opr > requirements.overall_pressure_ratio {
+            variables.hpc_pressure_ratio *= requirements.overall_pressure_ratio / opr;
+        }
+
+        requirements.constrain(self, bounds.clamp(variables))
+    }
+
+    /// Perform gradient ascent optimization to maximize thrust.
+    fn optimize_thrust(&self, mut variables: Variables, n: usize, bounds: &Bounds) -> Variables {
+        for _ in 0..n {
+            variables = self.step_thrust_optimization(variables, bounds);
+        }
+
+        variables
+    }
+
+    /// Perform gradient descent optimization to minimize an objective.
+    ///
+    /// A step that breaks a requirement is halved and retried, and the optimizer
+    /// stops once the step has been halved `OPTIMIZATION_BACKTRACKS` times,
+    /// returning the last design that met every requirement.  Returns `None` if
+    /// the starting design does not meet them.
+    fn optimize_sfc(&self, mut variables: Variables, objective: &Objective, requirements: &Requirements, bounds: &Bounds) -> Option<Variables> {
+        let mut value = objective.evaluate(self, variables).1;
+        if !requirements.violations(self, variables).is_empty() || !value.is_finite() {
+            return None;
+        }
+
+        let mut rate = OPTIMIZATION_RATE;
+        let mut backtracks = 0;
+        let mut i = 0;
+        while backtracks < OPTIMIZATION_BACKTRACKS {
+            let next = self.step_sfc_optimization(variables, rate, objective, requirements, bounds);
+            let (t_next, value_next) = objective.evaluate(self, next);
+
+            if value_next.is_finite() && requirements.violations(self, next).is_empty() {
+                (variables, value) = (next, value_next);
+
+                if i % 1_000_000 == 0 {
+                    println!("{}: {:.8} | {}: {:.8}", self.thrust_label(), t_next, objective.label(self), value);
+                }
+                i += 1;
+            } else {
+                rate *= 0.5;
+                backtracks += 1;
+            }
+        }
+
+        let (thrust, sfc) = self.analyze(variables);
+        println!("\nFinal performance\n{}", self.performance(thrust, sfc));
+        if !matches!(objective, Objective::Sfc) {
+            println!("Final {}: {:.8}\n", objective.label(self), value);
+        }
+        Some (variables)
+    }
+
+    /// Plot thrust as a function of one variable.
+    fn plot_thrust(
+        &self,
+        selected: VarSelector,
+        left: f64,
+        right: f64,
+        vars: Variables,
+    ) {
+        plot_output(self, selected, left, right, vars, |cycle| self.analyze_cycle(cycle).0, self.thrust_label(), "Thrust");
+    }
+
+    /// Plot specific fuel consumption as a function of one variable.
+    fn plot_sfc(
+        &self,
+        selected: VarSelector,
+        left: f64,
+        right: f64,
+        vars: Variables,
+    ) {
+        plot_output(self, selected, left, right, vars, |cycle| self.analyze_cycle(cycle).1, self.sfc_label(), "SFC");
+    }
+
+    /// Plot the NOx emissions index as a function of one variable.
+    ///
+    /// Engines without a combustor inlet station are not plotted.
+    fn plot_nox(
+        &self,
+        selected: VarSelector,
+        left: f64,
+        right: f64,
+        vars: Variables,
+    ) {
+        let nox = |cycle: &Cycle| Emissions::new(cycle, self.fuel()).map_or(f64::NAN, |e| e.nox_index);
+
+        plot_output(self, selected, left, right, vars, nox, "NOx Emissions Index (g/kg)", "NOx");
+    }
+
+    /// Plot jet noise as a function of one variable.
+    ///
+    /// Engines without a jet are not plotted.
+    fn plot_noise(
+        &self,
+        selected: VarSelector,
+        left: f64,
+        right: f64,
+        vars: Variables,
+    ) {
+        let noise = |cycle: &Cycle| Noise::new(cycle).map_or(f64::NAN, |noise| noise.level);
+
+        plot_output(self, selected, left, right, vars, noise, "Jet Noise OASPL (dB)", "Noise");
+    }
+
+    /// Plot specific fuel consumption against thrust along a throttle line at a flight condition,
+    /// and the HPC operating line on its map.
+    ///
+    /// The throttle setting runs from `left` to `right`; points where the engine
+    /// cannot be matched are skipped.
+    fn plot_throttle(
+        &self,
+        vars: Variables,
+        maps: &Maps,
+        condition: Condition,
+        setting: fn(f64) -> Throttle,
+        left: f64,
+        right: f64,
+    ) {
+        let n: usize = 40;
+
+        let points = (0..=n).collect::<Vec<usize>>();
+        let cycles = parallel::map_expensive(&points, |&i| {
+            let throttle = setting(left + (right - left)*(i as f64)/(n as f64));
+            self.off_design(vars, maps, Condition {
+                throttle,
+                ..condition
+            })
+        }).into_iter().flatten().collect::<Vec<Cycle>>();
+
+        if cycles.len() < 2 {
+            println!("[ERROR] The off-design solver did not converge along the throttle line");
+            return;
+        }
+
+        plot_series(
+            cycles.iter().map(|c| (c.thrust, c.sfc)).collect(),
+            self.thrust_label(),
+            self.sfc_label(),
+            &format!("{} Throttle Plot.png", self.name()),
+        );
+
+        if let Some (map) = self.compressor_map(vars, maps) {
+            let operating = cycles.iter()
+                .filter_map(|c| c.map_points.iter().find(|p| p.name == "HPC"))
+                .map(|p| (p.flow, p.ratio))
+                .collect();
+
+            plot_map(
+                &map,
+                operating,
+                "HPC",
+                &format!("{} HPC Operating Line.png", self.name()),
+            );
+        }
+    }
+}
+
+/// Plots an output of an engine, taken from its cycle, as a function of one variable.
+///
+/// Outputs that the engine does not give at the design `vars` are not plotted.
+#[allow(clippy::too_many_arguments)]
+fn plot_output<E, F>(
+    engine: &E,
+    selected: VarSelector,
+    left: f64,
+    right: f64,
+    vars: Variables,
+    output: F,
+    label: &str,
+    name: &str,
+) where
+    E: Engine + ?Sized,
+    F: Fn(&Cycle) -> f64 + Sync,
+{
+    if output(&engine.cycle(vars)).is_nan() {
+        return;
+    }
+
+    let n: usize = 1000;
+
+    let value = |input| {
+        let mut variables = vars;
+        *variables.get_reference(selected) = input;
+        output(&engine.cycle(variables))
+    };
+
+    let varname: String = selected.into();
+
+    plot(
+        value,
+        left,
+        right,
+        n,
+        &varname,
+        label,
+        &format!("{} {} {} Plot.png", engine.name(), varname, name),
+    );
+}
@@ -0,0 +1,335 @@
+//! Provides compressible flow and component relations for a calorically perfect gas.
+
+use crate::{
+    constants::*,
+    gas::Gas,
+    Variables,
+};
+
+/// Computes the ratio of total to static temperature at a given Mach number.
+pub fn total_temperature_ratio(mach: f64, gamma: f64) -> f64 {
+    1.0 + (gamma - 1.0)/2.0 * mach.powi(2)
+}
+
+/// Computes the ratio of total to static pressure at a given Mach number.
+pub fn total_pressure_ratio(mach: f64, gamma: f64) -> f64 {
+    total_temperature_ratio(mach, gamma).powf(gamma/(gamma - 1.0))
+}
+
+/// Computes the Mach number reached by expanding from a given total-to-static pressure ratio.
+pub fn expansion_mach(pressure_ratio: f64, gamma: f64) -> f64 {
+    (2.0 / (gamma - 1.0) * (pressure_ratio.powf((gamma - 1.0)/gamma) - 1.0)).sqrt()
+}
+
+/// Computes the mass flow parameter `mdot * sqrt(Tt) / (Pt * A)` at a given Mach number.
+pub fn mass_flow_parameter(mach: f64, gamma: f64) -> f64 {
+    (gamma / R).sqrt() * mach * total_temperature_ratio(mach, gamma).powf((gamma + 1.0)/(2.0*(1.0 - gamma)))
+}
+
+/// Computes the flow area (in m2) required to pass a mass flow at given total conditions and Mach number.
+pub fn flow_area(mdot: f64, t_t: f64, p_t: f64, mach: f64, gamma: f64) -> f64 {
+    mdot * t_t.sqrt() / (p_t * mass_flow_parameter(mach, gamma))
+}
+
+/// Computes the velocity (in m/s) of a stream fully expanded from total conditions to a static pressure `p`.
+pub fn exit_velocity(t_t: f64, p_t: f64, p: f64, gamma: f64) -> f64 {
+    let m = expansion_mach(p_t / p, gamma);
+    let t = t_t / total_temperature_ratio(m, gamma);
+    m * (gamma * R * t).sqrt()
+}
+
+/// Computes the ratio of total temperature to its thermally choked value in Rayleigh flow.
+pub fn rayleigh_temperature_ratio(mach: f64, gamma: f64) -> f64 {
+    2.0*(gamma + 1.0)*mach.powi(2) / (1.0 + gamma*mach.powi(2)).powi(2) * total_temperature_ratio(mach, gamma)
+}
+
+/// Computes the ratio of total pressure to its thermally choked value in Rayleigh flow.
+pub fn rayleigh_pressure_ratio(mach: f64, gamma: f64) -> f64 {
+    (gamma + 1.0)/(1.0 + gamma*mach.powi(2)) * (2.0/(gamma + 1.0) * total_temperature_ratio(mach, gamma)).powf(gamma/(gamma - 1.0))
+}
+
+/// Computes the subsonic Mach number at a given Rayleigh total temperature ratio.
+pub fn rayleigh_mach(temperature_ratio: f64, gamma: f64) -> f64 {
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..60 {
+        let mach = 0.5*(low + high);
+        if rayleigh_temperature_ratio(mach, gamma) < temperature_ratio {
+            low = mach;
+        } else {
+            high = mach;
+        }
+    }
+
+    0.5*(low + high)
+}
+
+/// Computes the total pressure recovery of a supersonic inlet according to MIL-E-5008B.
+pub fn inlet_recovery(mach: f64) -> f64 {
+    if mach <= 1.0 {
+        1.0
+    } else if mach < 5.0 {
+        1.0 - 0.075*(mach - 1.0).powf(1.35)
+    } else {
+        800.0 / (mach.powi(4) + 935.0)
+    }
+}
+
+/// Computes the inlet mass flow rate (in kg/s) from the inlet area and Mach number.
+pub fn inlet_mass_flow(variables: Variables) -> f64 {
+    P0 * variables.inlet_area() / T0.sqrt() * mass_flow_parameter(variables.inlet_mach_number, GAMMA)
+}
+
+/// Computes the exit total temperature of an adiabatic compressor.
+pub fn compressor_exit_temp(t_t: f64, pi: f64, eta: f64) -> f64 {
+    t_t * (1.0 + 1.0/eta * (pi.powf((GAMMA - 1.0)/GAMMA) - 1.0))
+}
+
+/// Computes the total pressure ratio across an adiabatic turbine from its inlet and exit total temperatures.
+pub fn turbine_pressure_ratio(t_in: f64, t_out: f64, eta: f64) -> f64 {
+    (1.0 - 1.0/eta * (1.0 - t_out/t_in)).powf(GAMMA_FLUE/(GAMMA_FLUE - 1.0))
+}
+
+/// Computes the exit total temperature of an adiabatic turbine from its total pressure ratio.
+pub fn turbine_exit_temp(t_in: f64, pi: f64, eta: f64) -> f64 {
+    t_in * (1.0 - eta * (1.0 - pi.powf((GAMMA_FLUE - 1.0)/GAMMA_FLUE)))
+}
+
+/// Computes the isentropic efficiency of a compressor from its polytropic efficiency.
+pub fn compressor_isentropic_efficiency(pi: f64, e: f64, gamma: f64) -> f64 {
+    if pi <= 1.0 {
+        return e;
+    }
+
+    let k = (gamma - 1.0)/gamma;
+    (pi.powf(k) - 1.0) / (pi.powf(k/e) - 1.0)
+}
+
+/// Computes the polytropic efficiency of a compressor from its isentropic efficiency.
+pub fn compressor_polytropic_efficiency(pi: f64, eta: f64, gamma: f64) -> f64 {
+    if pi <= 1.0 {
+        return eta;
+    }
+
+    let k = (gamma - 1.0)/gamma;
+    k * pi.ln() / (1.0 + (pi.powf(k) - 1.0)/eta).ln()
+}
+
+/// Computes the isentropic efficiency of a turbine from its total temperature ratio and polytropic efficiency.
+pub fn turbine_isentropic_efficiency(tau: f64, e: f64) -> f64 {
+    if tau >= 1.0 {
+        return e;
+    }
+
+    (1.0 - tau) / (1.0 - tau.powf(1.0/e))
+}
+
+/// Computes the polytropic efficiency of a turbine from its total temperature and pressure ratios.
+pub fn turbine_polytropic_efficiency(tau: f64, pi: f64, gamma: f64) -> f64 {
+    if tau >= 1.0 {
+        return 1.0;
+    }
+
+    tau.ln() / ((gamma - 1.0)/gamma * pi.ln())
+}
+
+/// Holds the state of one stream entering or leaving a mixer.
+#[derive(Clone, Copy, Debug)]
+pub struct Stream {
+    /// Mass flow rate (in kg/s or per unit of inlet mass flow)
+    pub mdot: f64,
+
+    /// Total temperature (in K)
+    pub t_t: f64,
+
+    /// Total pressure (in Pa)
+    pub p_t: f64,
+
+    /// Specific heat capacity at constant pressure (in J/kg-K)
+    pub cp: f64,
+
+    /// Ratio of specific heats
+    pub gamma: f64,
+
+    /// Fuel-air ratio
+    pub far: f64,
+}
+
+/// Mixes two streams in a constant-area duct.
+///
+/// The stream with the lower total pressure enters at Mach number `mach` and the
+/// other stream enters at the same static pressure.  Mass, momentum and energy are
+/// conserved across the duct with properties frozen at the entry conditions, and the
+/// ideal mixed-out total pressure is scaled by `efficiency`.
+///
+/// Returns NaN quantities if the mixed-out flow would choke.
+pub fn mix(first: Stream, second: Stream, mach: f64, efficiency: f64) -> Stream {
+    let (low, high) = if first.p_t <= second.p_t {
+        (first, second)
+    } else {
+        (second, first)
+    };
+
+    let gamma_l = low.gamma;
+    let gamma_h = high.gamma;
+
+    // Static pressure match at the mixer entry
+    let p = low.p_t / total_pressure_ratio(mach, gamma_l);
+    let mach_h = expansion_mach(high.p_t / p, gamma_h);
+
+    // Entry flow areas
+    let a_l = flow_area(low.mdot, low.t_t, low.p_t, mach, gamma_l);
+    let a_h = flow_area(high.mdot, high.t_t, high.p_t, mach_h, gamma_h);
+
+    // Conservation of mass and energy
+    let mdot = low.mdot + high.mdot;
+    let cp = (low.mdot*low.cp + high.mdot*high.cp) / mdot;
+    let t_t = (low.mdot*low.cp*low.t_t + high.mdot*high.cp*high.t_t) / (mdot*cp);
+    let gamma = cp / (cp - R);
+    let air = low.mdot/(1.0 + low.far) + high.mdot/(1.0 + high.far);
+    let far = (mdot - air) / air;
+
+    // Conservation of momentum (impulse function)
+    let impulse = p*a_l*(1.0 + gamma_l*mach.powi(2)) + p*a_h*(1.0 + gamma_h*mach_h.powi(2));
+
+    // Subsonic solution for the mixed-out Mach number
+    let phi = (mdot / impulse).powi(2) * R * t_t / gamma;
+    let mach = (2.0*phi / (1.0 - 2.0*gamma*phi + (1.0 - 2.0*(gamma + 1.0)*phi).sqrt())).sqrt();
+
+    let p_t = mdot * t_t.sqrt() / ((a_l + a_h) * mass_flow_parameter(mach, gamma));
+
+    Stream {
+        mdot,
+        t_t,
+        p_t: efficiency * p_t,
+        cp,
+        gamma,
+        far,
+    }
+}
+
+/// Burns fuel in an afterburner to raise a stream to the afterburner exit temperature.
+///
+/// Returns the reheated stream and the fuel burned per unit of inlet mass flow.
+pub fn reheat(gas: &Gas, stream: Stream, variables: Variables) -> (Stream, f64) {
+    let t_t7 = variables.afterburner_exit_temp.max(stream.t_t);
+    let fuel = stream.mdot * gas.burner_fuel(stream.t_t, t_t7, stream.far, variables.afterburner_efficiency, variables.fuel_delta_h);
+
+    let air = stream.mdot / (1.0 + stream.far);
+    let far = (stream.mdot + fuel - air) / air;
+    let stream = gas.stream(stream.mdot + fuel, t_t7, variables.afterburner_pressure_recovery * stream.p_t, far);
+
+    (stream, fuel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gas::GasModel,
+        Fuel,
+    };
+
+    /// Checks that two values agree to a relative tolerance.
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!((actual - expected).abs() <= tolerance * expected.abs(), "{} != {}", actual, expected);
+    }
+
+    /// Builds a stream of air at a given total temperature and pressure.
+    fn air(mdot: f64, t_t: f64, p_t: f64) -> Stream {
+        Stream {
+            mdot,
+            t_t,
+            p_t,
+            cp: CP_AIR,
+            gamma: GAMMA,
+            far: 0.0,
+        }
+    }
+
+    #[test]
+    fn mixing_identical_streams_leaves_them_unchanged() {
+        let stream = air(50.0, 400.0, 150000.0);
+        let mixed = mix(stream, stream, 0.4, 1.0);
+
+        assert_close(mixed.mdot, 2.0 * stream.mdot, 1.0E-12);
+        assert_close(mixed.t_t, stream.t_t, 1.0E-12);
+        assert_close(mixed.p_t, stream.p_t, 1.0E-9);
+        assert_close(mixed.gamma, stream.gamma, 1.0E-12);
+    }
+
+    #[test]
+    fn mixing_conserves_enthalpy_and_loses_pressure() {
+        let cold = air(80.0, 350.0, 160000.0);
+        let hot = Stream {
+            cp: 1150.0,
+            gamma: GAMMA_FLUE,
+            far: 0.02,
+            ..air(20.0, 800.0, 165000.0)
+        };
+
+        let ideal = mix(cold, hot, 0.4, 1.0);
+        let mixed = mix(cold, hot, 0.4, 0.98);
+
+        let enthalpy = |s: Stream| s.mdot * s.cp * s.t_t;
+        assert_close(enthalpy(mixed), enthalpy(cold) + enthalpy(hot), 1.0E-12);
+        assert!(mixed.t_t > cold.t_t && mixed.t_t < hot.t_t);
+
+        assert_close(mixed.p_t, 0.98 * ideal.p_t, 1.0E-12);
+        assert!(mixed.p_t < cold.p_t.min(hot.p_t));
+    }
+
+    #[test]
+    fn compressor_efficiencies_round_trip() {
+        let e = 0.90;
+
+        let mut previous = e;
+        for pi in [1.5, 5.0, 20.0, 50.0] {
+            let eta = compressor_isentropic_efficiency(pi, e, GAMMA);
+            assert_close(compressor_polytropic_efficiency(pi, eta, GAMMA), e, 1.0E-12);
+            assert!(eta < previous, "isentropic efficiency {} at {} is not below {}", eta, pi, previous);
+            previous = eta;
+        }
+    }
+
+    #[test]
+    fn turbine_efficiencies_round_trip() {
+        let e = 0.90;
+        let k = (GAMMA_FLUE - 1.0)/GAMMA_FLUE;
+
+        for pi in [0.5_f64, 0.2, 0.05] {
+            let tau = pi.powf(e * k);
+            let eta = turbine_isentropic_efficiency(tau, e);
+
+            assert_close(tau, 1.0 - eta*(1.0 - pi.powf(k)), 1.0E-12);
+            assert_close(turbine_polytropic_efficiency(tau, pi, GAMMA_FLUE), e, 1.0E-12);
+            assert!(eta > e);
+        }
+    }
+
+    #[test]
+    fn rayleigh_mach_inverts_temperature_ratio() {
+        for mach in [0.05, 0.2, 0.5, 0.8, 0.99] {
+            let ratio = rayleigh_temperature_ratio(mach, GAMMA_FLUE);
+            assert_close(rayleigh_mach(ratio, GAMMA_FLUE), mach, 1.0E-9);
+        }
+
+        assert_close(rayleigh_temperature_ratio(1.0, GAMMA_FLUE), 1.0, 1.0E-12);
+        assert_close(rayleigh_pressure_ratio(1.0, GAMMA_FLUE), 1.0, 1.0E-12);
+    }
+
+    #[test]
+    fn reheat_reaches_exit_temperature() {
+        let variables = VANILLA_PLUS;
+        let gas = Gas::new(GasModel::Perfect, Fuel::default(), variables);
+        let stream = gas.stream(1.0, 900.0, 200000.0, 0.02);
+
+        let (reheated, fuel) = reheat(&gas, stream, variables);
+
+        let delta_t = variables.afterburner_exit_temp - stream.t_t;
+        let expected = stream.mdot * variables.fuel_cp * delta_t / (variables.afterburner_efficiency * variables.fuel_delta_h);
+        assert_close(fuel, expected, 1.0E-12);
+        assert_close(reheated.t_t, variables.afterburner_exit_temp, 1.0E-12);
+        assert_close(reheated.mdot, stream.mdot + fuel, 1.0E-12);
+        assert_close(reheated.p_t, variables.afterburner_pressure_recovery * stream.p_t, 1.0E-12);
+    }
+}
\ No newline at end of file
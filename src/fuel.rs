@@ -0,0 +1,160 @@
+//! Holds a database of aviation fuels.
+//!
+//! Each fuel is described by its lower heating value, the specific heat capacity
+//! of its combustion products for the perfect gas model, and its carbon mass
+//! fraction.  The stoichiometric fuel-air ratio and the CO2 and H2O emissions
+//! indices follow from complete combustion of the fuel's carbon and hydrogen.
+//! Sustainable fuels emit as much CO2 in combustion as fossil kerosene, so
+//! their benefit is carried by a net lifecycle CO2 factor.
+
+use std::{
+    fmt::{
+        self,
+        Display,
+        Formatter,
+    },
+    process,
+};
+
+/// Molar masses of carbon and hydrogen (in kg/mol).
+pub(crate) const M_CARBON: f64 = 0.012011;
+pub(crate) const M_HYDROGEN: f64 = 0.001008;
+
+/// Molar masses of O2, CO2 and H2O (in kg/mol).
+const M_OXYGEN: f64 = 0.0319988;
+const M_CO2: f64 = 0.0440095;
+const M_H2O: f64 = 0.01801528;
+
+/// Mass fraction of O2 in dry air.
+const O2_MASS_FRACTION: f64 = 0.2314;
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the properties of one fuel.
+pub struct Fuel {
+    /// Fuel name, as given to `--fuel`
+    pub name: &'static str,
+
+    /// Lower heating value (in J/kg)
+    pub delta_h: f64,
+
+    /// Specific heat capacity of combustion products for the perfect gas model (in J/kg-K)
+    pub cp: f64,
+
+    /// Mass fraction of carbon, the rest being hydrogen
+    pub carbon_fraction: f64,
+
+    /// Net lifecycle CO2, as a fraction of the CO2 emitted in combustion
+    pub lifecycle_co2: f64,
+}
+
+/// Fuels available to `--fuel`.
+pub const FUELS: [Fuel; 6] = [
+    Fuel {
+        name: "jet-a",
+        delta_h: 43.0E+6,
+        cp: 1148.4941,
+        carbon_fraction: 0.862,
+        lifecycle_co2: 1.0,
+    },
+    Fuel {
+        name: "jp-8",
+        delta_h: 42.8E+6,
+        cp: 1148.4941,
+        carbon_fraction: 0.859,
+        lifecycle_co2: 1.0,
+    },
+    Fuel {
+        name: "saf-50",
+        delta_h: 43.5E+6,
+        cp: 1149.5,
+        carbon_fraction: 0.855,
+        lifecycle_co2: 0.6,
+    },
+    Fuel {
+        name: "saf",
+        delta_h: 44.1E+6,
+        cp: 1150.5,
+        carbon_fraction: 0.847,
+        lifecycle_co2: 0.2,
+    },
+    Fuel {
+        name: "lng",
+        delta_h: 50.0E+6,
+        cp: 1180.0,
+        carbon_fraction: 0.7487,
+        lifecycle_co2: 1.0,
+    },
+    Fuel {
+        name: "lh2",
+        delta_h: 120.0E+6,
+        cp: 1250.0,
+        carbon_fraction: 0.0,
+        lifecycle_co2: 1.0,
+    },
+];
+
+impl Default for Fuel {
+    /// Selects Jet-A.
+    fn default() -> Self {
+        FUELS[0]
+    }
+}
+
+impl From<String> for Fuel {
+    fn from(string: String) -> Self {
+        match FUELS.iter().find(|fuel| fuel.name == string.as_str()) {
+            Some (&fuel) => fuel,
+            None => {
+                println!("[FATAL] Invalid fuel {}.  Type `jetlab` for help.", &string);
+                process::exit(0);
+            },
+        }
+    }
+}
+
+impl Fuel {
+    /// Computes the stoichiometric fuel-air ratio.
+    pub fn stoichiometric_ratio(&self) -> f64 {
+        let oxygen = (self.carbon_fraction/M_CARBON + (1.0 - self.carbon_fraction)/(4.0*M_HYDROGEN)) * M_OXYGEN;
+
+        O2_MASS_FRACTION / oxygen
+    }
+
+    /// Computes the CO2 emissions index (in g/kg of fuel).
+    pub fn ei_co2(&self) -> f64 {
+        1000.0 * self.carbon_fraction * M_CO2/M_CARBON
+    }
+
+    /// Computes the H2O emissions index (in g/kg of fuel).
+    pub fn ei_h2o(&self) -> f64 {
+        1000.0 * (1.0 - self.carbon_fraction) * M_H2O/(2.0*M_HYDROGEN)
+    }
+}
+
+impl Display for Fuel {
+    /// Format this data structure.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+
+        output.push_str(&format!("Fuel: {}\n", self.name));
+        output.push_str(&format!("Lower heating value: {:.3} MJ/kg\n", self.delta_h/1.0E+6));
+        output.push_str(&format!("Stoichiometric fuel-air ratio: {:.5}\n", self.stoichiometric_ratio()));
+        output.push_str(&format!("CO2 emissions index: {:.1} g/kg\n", self.ei_co2()));
+        output.push_str(&format!("H2O emissions index: {:.1} g/kg\n", self.ei_h2o()));
+
+        write!(f, "{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gives_stoichiometric_ratios() {
+        for (name, expected) in [("jet-a", 0.068), ("lh2", 0.029)] {
+            let ratio = Fuel::from(name.to_string()).stoichiometric_ratio();
+            assert!((ratio - expected).abs() <= 0.001, "{}: {} != {}", name, ratio, expected);
+        }
+    }
+}
\ No newline at end of file
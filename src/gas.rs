@@ -0,0 +1,425 @@
+//! Provides thermodynamic properties of air and combustion products.
+
+use std::process;
+
+use crate::{
+    constants::*,
+    flow::{
+        self,
+        Stream,
+    },
+    fuel::{
+        M_CARBON,
+        M_HYDROGEN,
+    },
+    Fuel,
+    Variables,
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Selects how gas properties are computed.
+pub enum GasModel {
+    /// Constant properties for air (`CP_AIR`, `GAMMA`) and for combustion products (`fuel_cp`, `GAMMA_FLUE`)
+    #[default]
+    Perfect,
+
+    /// Temperature- and composition-dependent properties from NASA 7-coefficient polynomials
+    Nasa,
+}
+
+impl From<String> for GasModel {
+    fn from(string: String) -> Self {
+        match string.as_str() {
+            "perfect" => GasModel::Perfect,
+            "nasa" => GasModel::Nasa,
+            _ => {
+                println!("[FATAL] Invalid gas model {}.  Type `jetlab` for help.", &string);
+                process::exit(0);
+            },
+        }
+    }
+}
+
+/// Universal gas constant (in J/mol-K).
+const R_UNIVERSAL: f64 = 8.314462618;
+
+/// Reference temperature for sensible enthalpies and the fuel heating value (in K).
+const T_REF: f64 = 298.15;
+
+/// Holds NASA 7-coefficient polynomials for one species.
+struct Species {
+    /// Molar mass (in kg/mol)
+    molar_mass: f64,
+
+    /// Coefficients below 1000 K
+    low: [f64; 7],
+
+    /// Coefficients above 1000 K
+    high: [f64; 7],
+}
+
+/// Species in air and combustion products: N2, O2, Ar, CO2 and H2O (GRI-Mech 3.0 thermodynamic data).
+const SPECIES: [Species; 5] = [
+    Species {
+        molar_mass: 0.0280134,
+        low: [3.298677, 1.4082404E-3, -3.963222E-6, 5.641515E-9, -2.444854E-12, -1020.8999, 3.950372],
+        high: [2.92664, 1.4879768E-3, -5.68476E-7, 1.0097038E-10, -6.753351E-15, -922.7977, 5.980528],
+    },
+    Species {
+        molar_mass: 0.0319988,
+        low: [3.78245636, -2.99673416E-3, 9.84730201E-6, -9.68129509E-9, 3.24372837E-12, -1063.94356, 3.65767573],
+        high: [3.28253784, 1.48308754E-3, -7.57966669E-7, 2.09470555E-10, -2.16717794E-14, -1088.45772, 5.45323129],
+    },
+    Species {
+        molar_mass: 0.039948,
+        low: [2.5, 0.0, 0.0, 0.0, 0.0, -745.375, 4.366],
+        high: [2.5, 0.0, 0.0, 0.0, 0.0, -745.375, 4.366],
+    },
+    Species {
+        molar_mass: 0.0440095,
+        low: [2.35677352, 8.98459677E-3, -7.12356269E-6, 2.45919022E-9, -1.43699548E-13, -48371.9697, 9.90105222],
+        high: [3.85746029, 4.41437026E-3, -2.21481404E-6, 5.23490188E-10, -4.72084164E-14, -48759.166, 2.27163806],
+    },
+    Species {
+        molar_mass: 0.01801528,
+        low: [4.19864056, -2.0364341E-3, 6.52040211E-6, -5.48797062E-9, 1.77197817E-12, -30293.7267, -0.849032208],
+        high: [3.03399249, 2.17691804E-3, -1.64072518E-7, -9.7041987E-11, 1.68200992E-14, -30004.2971, 4.9667701],
+    },
+];
+
+/// Mole fractions of N2, O2, Ar and CO2 in dry air.
+const AIR: [f64; 4] = [0.78084, 0.209476, 0.00934, 0.000314];
+
+impl Species {
+    /// Selects the coefficients valid at a given temperature.
+    fn coefficients(&self, t: f64) -> &[f64; 7] {
+        if t < 1000.0 {
+            &self.low
+        } else {
+            &self.high
+        }
+    }
+
+    /// Computes the specific heat capacity at constant pressure (in J/kg-K).
+    fn cp(&self, t: f64) -> f64 {
+        let a = self.coefficients(t);
+        R_UNIVERSAL / self.molar_mass * (a[0] + t*(a[1] + t*(a[2] + t*(a[3] + t*a[4]))))
+    }
+
+    /// Computes the absolute enthalpy (in J/kg).
+    fn enthalpy(&self, t: f64) -> f64 {
+        let a = self.coefficients(t);
+        R_UNIVERSAL / self.molar_mass * (t*(a[0] + t*(a[1]/2.0 + t*(a[2]/3.0 + t*(a[3]/4.0 + t*a[4]/5.0)))) + a[5])
+    }
+
+    /// Computes the standard-state entropy (in J/kg-K).
+    fn entropy(&self, t: f64) -> f64 {
+        let a = self.coefficients(t);
+        R_UNIVERSAL / self.molar_mass * (a[0]*t.ln() + t*(a[1] + t*(a[2]/2.0 + t*(a[3]/3.0 + t*a[4]/4.0))) + a[6])
+    }
+}
+
+/// Computes the mass fractions of each species at a given fuel-air ratio, assuming complete lean combustion
+/// of a fuel with a given carbon mass fraction.
+fn mass_fractions(far: f64, carbon_fraction: f64) -> [f64; 5] {
+    // Moles of each species per kg of air
+    let m_air: f64 = AIR.iter().zip(SPECIES.iter()).map(|(x, s)| x*s.molar_mass).sum();
+    let mut moles = [0.0; 5];
+    for i in 0..4 {
+        moles[i] = AIR[i] / m_air;
+    }
+
+    // Complete combustion of the fuel's carbon and hydrogen
+    let carbon = far * carbon_fraction / M_CARBON;
+    let hydrogen = far * (1.0 - carbon_fraction) / M_HYDROGEN;
+    moles[1] = (moles[1] - carbon - hydrogen/4.0).max(0.0);
+    moles[3] += carbon;
+    moles[4] += hydrogen/2.0;
+
+    let mut fractions = [0.0; 5];
+    for i in 0..5 {
+        fractions[i] = moles[i] * SPECIES[i].molar_mass / (1.0 + far);
+    }
+
+    fractions
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Computes gas properties and component processes for one cycle analysis.
+pub struct Gas {
+    /// Gas model
+    pub model: GasModel,
+
+    /// Specific heat capacity of combustion products for the perfect gas model (in J/kg-K)
+    pub cp_flue: f64,
+
+    /// Fuel burned, which sets the composition of combustion products for the NASA model
+    pub fuel: Fuel,
+}
+
+impl Gas {
+    /// Constructs a new gas for the given fuel and design variables.
+    pub fn new(model: GasModel, fuel: Fuel, variables: Variables) -> Self {
+        Self {
+            model,
+            cp_flue: variables.fuel_cp,
+            fuel,
+        }
+    }
+
+    /// Computes the specific heat capacity at constant pressure (in J/kg-K).
+    pub fn cp(&self, t: f64, far: f64) -> f64 {
+        match self.model {
+            GasModel::Perfect => if far > 0.0 {
+                self.cp_flue
+            } else {
+                CP_AIR
+            },
+            GasModel::Nasa => mass_fractions(far, self.fuel.carbon_fraction).iter().zip(SPECIES.iter()).map(|(y, s)| y*s.cp(t)).sum(),
+        }
+    }
+
+    /// Computes the specific gas constant (in J/kg-K).
+    pub fn r(&self, far: f64) -> f64 {
+        match self.model {
+            GasModel::Perfect => R,
+            GasModel::Nasa => mass_fractions(far, self.fuel.carbon_fraction).iter().zip(SPECIES.iter()).map(|(y, s)| y*R_UNIVERSAL/s.molar_mass).sum(),
+        }
+    }
+
+    /// Computes the ratio of specific heats.
+    pub fn gamma(&self, t: f64, far: f64) -> f64 {
+        match self.model {
+            GasModel::Perfect => if far > 0.0 {
+                GAMMA_FLUE
+            } else {
+                GAMMA
+            },
+            GasModel::Nasa => {
+                let cp = self.cp(t, far);
+                cp / (cp - self.r(far))
+            },
+        }
+    }
+
+    /// Computes the constant ratio of specific heats that reproduces the isentropic
+    /// temperature ratio of this gas over a given total pressure ratio.
+    pub fn effective_gamma(&self, t_in: f64, pi: f64, far: f64) -> f64 {
+        match self.model {
+            GasModel::Nasa if pi != 1.0 => {
+                let t_s = self.temperature_at_entropy(self.entropy(t_in, far) + self.r(far)*pi.ln(), far, t_in);
+                1.0 / (1.0 - (t_s/t_in).ln()/pi.ln())
+            },
+            _ => self.gamma(t_in, far),
+        }
+    }
+
+    /// Computes the sensible enthalpy relative to `T_REF` (in J/kg).
+    pub fn enthalpy(&self, t: f64, far: f64) -> f64 {
+        match self.model {
+            GasModel::Perfect => self.cp(t, far) * (t - T_REF),
+            GasModel::Nasa => mass_fractions(far, self.fuel.carbon_fraction).iter().zip(SPECIES.iter()).map(|(y, s)| y*(s.enthalpy(t) - s.enthalpy(T_REF))).sum(),
+        }
+    }
+
+    /// Computes the standard-state entropy function (in J/kg-K).
+    fn entropy(&self, t: f64, far: f64) -> f64 {
+        match self.model {
+            GasModel::Perfect => self.cp(t, far) * t.ln(),
+            GasModel::Nasa => mass_fractions(far, self.fuel.carbon_fraction).iter().zip(SPECIES.iter()).map(|(y, s)| y*s.entropy(t)).sum(),
+        }
+    }
+
+    /// Computes the temperature (in K) at a given sensible enthalpy.
+    pub fn temperature(&self, h: f64, far: f64) -> f64 {
+        let mut t = T_REF + h/self.cp(1000.0, far);
+        for _ in 0..50 {
+            let dt = (self.enthalpy(t, far) - h) / self.cp(t, far);
+            t -= dt;
+            if dt.abs() < 1.0E-9 {
+                break;
+            }
+        }
+
+        t
+    }
+
+    /// Computes the temperature (in K) at a given entropy function.
+    fn temperature_at_entropy(&self, s: f64, far: f64, guess: f64) -> f64 {
+        let mut t = guess;
+        for _ in 0..50 {
+            let dt = (self.entropy(t, far) - s) * t / self.cp(t, far);
+            t -= dt;
+            if dt.abs() < 1.0E-9 {
+                break;
+            }
+        }
+
+        t
+    }
+
+    /// Constructs a stream with the properties of this gas at its total temperature.
+    pub fn stream(&self, mdot: f64, t_t: f64, p_t: f64, far: f64) -> Stream {
+        Stream {
+            mdot,
+            t_t,
+            p_t,
+            cp: self.cp(t_t, far),
+            gamma: self.gamma(t_t, far),
+            far,
+        }
+    }
+
+    /// Mixes two streams at the lower of their total pressures, conserving mass and enthalpy.
+    pub fn merge(&self, first: Stream, second: Stream) -> Stream {
+        let mdot = first.mdot + second.mdot;
+        let h = (first.mdot*self.enthalpy(first.t_t, first.far) + second.mdot*self.enthalpy(second.t_t, second.far)) / mdot;
+        let air = first.mdot/(1.0 + first.far) + second.mdot/(1.0 + second.far);
+        let far = (mdot - air) / air;
+
+        self.stream(mdot, self.temperature(h, far), first.p_t.min(second.p_t), far)
+    }
+
+    /// Computes the exit total temperature of an adiabatic compressor of air.
+    pub fn compressor_exit_temp(&self, t_in: f64, pi: f64, eta: f64) -> f64 {
+        match self.model {
+            GasModel::Perfect => flow::compressor_exit_temp(t_in, pi, eta),
+            GasModel::Nasa => {
+                let t_s = self.temperature_at_entropy(self.entropy(t_in, 0.0) + self.r(0.0)*pi.ln(), 0.0, t_in);
+                let h_in = self.enthalpy(t_in, 0.0);
+                self.temperature(h_in + (self.enthalpy(t_s, 0.0) - h_in)/eta, 0.0)
+            },
+        }
+    }
+
+    /// Computes the work (in J/kg) absorbed by a compressor of air between two total temperatures.
+    pub fn compressor_work(&self, t_in: f64, t_out: f64) -> f64 {
+        self.enthalpy(t_out, 0.0) - self.enthalpy(t_in, 0.0)
+    }
+
+    /// Computes the exit total temperature and total pressure ratio of an adiabatic
+    /// turbine delivering a given work (in J/kg of gas).
+    pub fn turbine(&self, t_in: f64, work: f64, eta: f64, far: f64) -> (f64, f64) {
+        match self.model {
+            GasModel::Perfect => {
+                let t_out = t_in - work/self.cp_flue;
+                (t_out, flow::turbine_pressure_ratio(t_in, t_out, eta))
+            },
+            GasModel::Nasa => {
+                let h_in = self.enthalpy(t_in, far);
+                let t_out = self.temperature(h_in - work, far);
+                let t_s = self.temperature(h_in - work/eta, far);
+                let pi = ((self.entropy(t_s, far) - self.entropy(t_in, far)) / self.r(far)).exp();
+                (t_out, pi)
+            },
+        }
+    }
+
+    /// Computes the exit total temperature of an adiabatic turbine with a given total pressure ratio.
+    pub fn turbine_exit_temp(&self, t_in: f64, pi: f64, eta: f64, far: f64) -> f64 {
+        match self.model {
+            GasModel::Perfect => flow::turbine_exit_temp(t_in, pi, eta),
+            GasModel::Nasa => {
+                let t_s = self.temperature_at_entropy(self.entropy(t_in, far) + self.r(far)*pi.ln(), far, t_in);
+                let h_in = self.enthalpy(t_in, far);
+                self.temperature(h_in - eta*(h_in - self.enthalpy(t_s, far)), far)
+            },
+        }
+    }
+
+    /// Computes the fuel burned per unit mass of a stream to raise it from `t_in` to `t_out`.
+    pub fn burner_fuel(&self, t_in: f64, t_out: f64, far: f64, efficiency: f64, delta_h: f64) -> f64 {
+        match self.model {
+            GasModel::Perfect => self.cp_flue * (t_out - t_in) / (efficiency * delta_h),
+            GasModel::Nasa => {
+                // Energy balance with sensible enthalpies, iterating on the product composition
+                let h_in = self.enthalpy(t_in, far);
+                let air = 1.0 / (1.0 + far);
+                let mut fuel = 0.0;
+                for _ in 0..20 {
+                    let far_out = (far*air + fuel) / air;
+                    let h_out = self.enthalpy(t_out, far_out);
+                    fuel = (h_out - h_in) / (efficiency*delta_h - h_out);
+                }
+
+                fuel
+            },
+        }
+    }
+
+    /// Computes the velocity (in m/s) of a stream fully expanded from total conditions to a static pressure `p`.
+    pub fn exit_velocity(&self, stream: Stream, p: f64) -> f64 {
+        match self.model {
+            GasModel::Perfect => flow::exit_velocity(stream.t_t, stream.p_t, p, stream.gamma),
+            GasModel::Nasa => {
+                let far = stream.far;
+                let s = self.entropy(stream.t_t, far) - self.r(far)*(stream.p_t/p).ln();
+                let t = self.temperature_at_entropy(s, far, stream.t_t);
+                (2.0 * (self.enthalpy(stream.t_t, far) - self.enthalpy(t, far))).sqrt()
+            },
+        }
+    }
+
+    /// Computes the static density (in kg/m3) of a stream fully expanded from total conditions to a static pressure `p`.
+    pub fn exit_density(&self, stream: Stream, p: f64) -> f64 {
+        let far = stream.far;
+        let t = match self.model {
+            GasModel::Perfect => {
+                let m = flow::expansion_mach(stream.p_t / p, stream.gamma);
+                stream.t_t / flow::total_temperature_ratio(m, stream.gamma)
+            },
+            GasModel::Nasa => {
+                let s = self.entropy(stream.t_t, far) - self.r(far)*(stream.p_t/p).ln();
+                self.temperature_at_entropy(s, far, stream.t_t)
+            },
+        };
+
+        p / (self.r(far) * t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a NASA-polynomial gas burning Jet-A.
+    fn nasa() -> Gas {
+        Gas::new(GasModel::Nasa, Fuel::default(), VANILLA_PLUS)
+    }
+
+    #[test]
+    fn air_cp_matches_janaf() {
+        let gas = nasa();
+
+        for (t, janaf) in [(300.0, 1005.0), (1500.0, 1216.0)] {
+            let cp = gas.cp(t, 0.0);
+            assert!((cp - janaf).abs() <= 0.005 * janaf, "cp at {} K: {} != {}", t, cp, janaf);
+        }
+    }
+
+    #[test]
+    fn burner_fuel_closes_energy_balance() {
+        let gas = nasa();
+        let (t_in, t_out, efficiency, delta_h) = (800.0, 1700.0, 0.99, gas.fuel.delta_h);
+
+        let fuel = gas.burner_fuel(t_in, t_out, 0.0, efficiency, delta_h);
+        let heat_in = gas.enthalpy(t_in, 0.0) + fuel*efficiency*delta_h;
+        let heat_out = (1.0 + fuel) * gas.enthalpy(t_out, fuel);
+
+        assert!((heat_in - heat_out).abs() <= 1.0E-9 * heat_out, "{} != {}", heat_in, heat_out);
+    }
+
+    #[test]
+    fn isentropic_compression_and_expansion_are_reversible() {
+        let gas = nasa();
+        let (t_in, pi, far) = (288.15, 20.0, 0.0);
+
+        let t_out = gas.compressor_exit_temp(t_in, pi, 1.0);
+        let t_back = gas.turbine_exit_temp(t_out, 1.0/pi, 1.0, far);
+        assert!((t_back - t_in).abs() <= 1.0E-6, "{} != {}", t_back, t_in);
+
+        let (t_out, pi) = gas.turbine(1600.0, 400.0E+3, 1.0, 0.02);
+        let t_exit = gas.turbine_exit_temp(1600.0, pi, 1.0, 0.02);
+        assert!((t_exit - t_out).abs() <= 1.0E-6, "{} != {}", t_exit, t_out);
+    }
+}
\ No newline at end of file
@@ -1,14 +1,119 @@
 //! Main library for the Jetlab.
 
+mod atmosphere;
+mod batch;
+mod bounds;
 mod cli;
 pub mod constants;
+mod cycle;
+mod doe;
+mod emissions;
+mod engine;
+mod flow;
+mod fuel;
+mod gas;
+mod maps;
+mod mission;
+mod nacelle;
+mod newton;
+mod noise;
+mod objective;
+mod offdesign;
+mod parallel;
+mod ramjet;
+mod requirements;
+mod sensitivity;
 mod turbofan;
+mod turbojet;
+mod turboprop;
+mod turboshaft;
+mod uncertainty;
 mod plot;
+mod random;
 mod variables;
 mod varselect;
+mod weight;
 
+pub use atmosphere::isa;
+pub use batch::Batch;
+pub use bounds::Bounds;
 pub use cli::Cli;
-pub use plot::plot;
-pub use turbofan::Turbofan;
+pub use cycle::{
+    Cycle,
+    Efficiency,
+    Jet,
+    Station,
+};
+pub use doe::{
+    Design,
+    Experiment,
+};
+pub use emissions::{
+    nox_index,
+    Emissions,
+};
+pub use engine::Engine;
+pub use fuel::{
+    Fuel,
+    FUELS,
+};
+pub use gas::GasModel;
+pub use maps::{
+    Map,
+    Maps,
+    ScaledMap,
+};
+pub use mission::{
+    Flight,
+    Mission,
+    Segment,
+};
+pub use nacelle::{
+    Installed,
+    Nacelle,
+};
+pub use noise::Noise;
+pub use objective::Objective;
+pub use offdesign::{
+    Condition,
+    Throttle,
+};
+pub use plot::{
+    plot,
+    plot_bars,
+    plot_histogram,
+    plot_map,
+    plot_series,
+};
+pub use random::{
+    Distribution,
+    Random,
+};
+pub use ramjet::Ramjet;
+pub use requirements::Requirements;
+pub use sensitivity::{
+    Indices,
+    Sensitivity,
+};
+pub use turbofan::{
+    BleedStation,
+    Cooling,
+    Exhaust,
+    Polytropic,
+    Spools,
+    Turbofan,
+};
+pub use turbojet::Turbojet;
+pub use turboprop::Turboprop;
+pub use turboshaft::Turboshaft;
+pub use uncertainty::{
+    Dispersion,
+    Statistics,
+    Uncertainty,
+};
 pub use variables::Variables;
-pub use varselect::VarSelector;
\ No newline at end of file
+pub use varselect::VarSelector;
+pub use weight::{
+    Module,
+    Size,
+};
\ No newline at end of file
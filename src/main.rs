@@ -1,27 +1,30 @@
 //! Main executable for the Jetlab.
 
+use std::path::Path;
+
 use jetlab::{
+    Batch,
     Cli,
-    Turbofan,
+    Emissions,
+    Installed,
+    Noise,
 };
 
 fn main() {
     // Read user input
     let cli = Cli::new();
 
-    // Set up a turbofan
-    let fan = Turbofan::new();
-
     if let Cli::Plot {
         variable,
         left,
         right,
         allvars,
+        engine,
     } = cli {
         let variables = allvars;
 
         // Plot thrust
-        fan.plot_thrust(
+        engine.plot_thrust(
             variable,
             left,
             right,
@@ -29,7 +32,23 @@ fn main() {
         );
         
         // Plot SFC
-        fan.plot_sfc(
+        engine.plot_sfc(
+            variable,
+            left,
+            right,
+            variables,
+        );
+
+        // Plot NOx
+        engine.plot_nox(
+            variable,
+            left,
+            right,
+            variables,
+        );
+
+        // Plot jet noise
+        engine.plot_noise(
             variable,
             left,
             right,
@@ -37,9 +56,19 @@ fn main() {
         );
     } else if let Cli::Optimize {
         allvars,
+        engine,
+        objective,
+        requirements,
+        bounds,
     } = cli {
-        // let thrust_opt = fan.optimize_thrust(allvars, steps);
-        let sfc_opt = fan.optimize_sfc(allvars);
+        // let thrust_opt = engine.optimize_thrust(allvars, steps, &bounds);
+        let Some (sfc_opt) = engine.optimize_sfc(allvars, &objective, &requirements, &bounds) else {
+            println!("[ERROR] The starting design does not meet the requirements");
+            for violation in requirements.violations(engine.as_ref(), allvars) {
+                println!("[WARNING] {}", violation);
+            }
+            return;
+        };
 
         println!("OPTIMIZATION RESULTS");
         println!("====================\n");
@@ -47,7 +76,196 @@ fn main() {
         // println!("Thrust focus");
         // println!("{}\n", thrust_opt);
 
-        println!("SFC focus");
+        println!("{} focus", objective.label(engine.as_ref()));
         println!("{}", sfc_opt);
+    } else if let Cli::Analyze {
+        allvars,
+        engine,
+        requirements,
+    } = cli {
+        println!("{} ANALYSIS", engine.name().to_uppercase());
+        println!("====================\n");
+
+        let (thrust, sfc) = engine.analyze(allvars);
+        println!("{}", engine.performance(thrust, sfc));
+
+        let cycle = engine.cycle(allvars);
+        println!("{}", cycle);
+
+        if let Some (emissions) = Emissions::new(&cycle, engine.fuel()) {
+            println!("{}", emissions);
+        }
+
+        if let Some (noise) = Noise::new(&cycle) {
+            println!("{}", noise);
+        }
+
+        if let Some (size) = engine.size(allvars) {
+            println!("{}", size);
+        }
+
+        let violations = requirements.violations(engine.as_ref(), allvars);
+        if violations.is_empty() {
+            println!("All requirements met");
+        }
+        for violation in violations {
+            println!("[WARNING] {}", violation);
+        }
+    } else if let Cli::OffDesign {
+        allvars,
+        engine,
+        maps,
+        condition,
+    } = cli {
+        println!("{} OFF-DESIGN ANALYSIS", engine.name().to_uppercase());
+        println!("====================\n");
+
+        println!("Altitude: {:.1} m", condition.altitude);
+        println!("Flight Mach number: {:.3}\n", condition.mach);
+
+        match engine.off_design(allvars, &maps, condition) {
+            Some (cycle) => {
+                println!("{}", engine.performance(cycle.thrust, cycle.sfc));
+                println!("{}", cycle);
+            },
+            None => println!("[ERROR] The off-design solver did not converge"),
+        }
+    } else if let Cli::Throttle {
+        setting,
+        left,
+        right,
+        allvars,
+        engine,
+        maps,
+        condition,
+    } = cli {
+        // Plot SFC against thrust and the HPC operating line
+        engine.plot_throttle(
+            allvars,
+            &maps,
+            condition,
+            setting,
+            left,
+            right,
+        );
+    } else if let Cli::Installed {
+        allvars,
+        engine,
+        maps,
+        condition,
+    } = cli {
+        println!("{} INSTALLED PERFORMANCE", engine.name().to_uppercase());
+        println!("====================\n");
+
+        println!("Altitude: {:.1} m", condition.altitude);
+        println!("Flight Mach number: {:.3}\n", condition.mach);
+
+        match Installed::new(engine.as_ref(), allvars, &maps, condition) {
+            Some (installed) => println!("{}", installed),
+            None => println!("[ERROR] The off-design solver did not converge"),
+        }
+    } else if let Cli::Mission {
+        allvars,
+        engine,
+        maps,
+        mission,
+    } = cli {
+        println!("{} MISSION ANALYSIS", engine.name().to_uppercase());
+        println!("====================\n");
+
+        println!("Takeoff mass: {:.1} kg", mission.takeoff_mass);
+        println!("Lift-to-drag ratio: {:.2}", mission.lift_to_drag);
+        println!("Cruise altitude: {:.1} m", mission.cruise_altitude);
+        println!("Cruise Mach number: {:.3}\n", mission.cruise_mach);
+
+        match mission.fly(engine.as_ref(), allvars, &maps) {
+            Some (flight) => println!("{}", flight),
+            None => println!("[ERROR] The engines could not fly this mission"),
+        }
+    } else if let Cli::Uncertainty {
+        allvars,
+        engine,
+        uncertainty,
+        bounds,
+    } = cli {
+        println!("{} UNCERTAINTY ANALYSIS", engine.name().to_uppercase());
+        println!("====================\n");
+
+        println!("Seed: {}\n", uncertainty.seed);
+
+        let dispersion = uncertainty.propagate(engine.as_ref(), allvars, &bounds);
+        if dispersion.thrust.values.len() < 2 {
+            println!("[ERROR] Too few samples could be analyzed");
+        } else {
+            println!("{}", dispersion);
+            dispersion.plot_histograms(&engine.name());
+        }
+    } else if let Cli::Sensitivity {
+        allvars,
+        engine,
+        sensitivity,
+        bounds,
+    } = cli {
+        println!("{} SENSITIVITY ANALYSIS", engine.name().to_uppercase());
+        println!("====================\n");
+
+        println!("Seed: {}\n", sensitivity.seed);
+
+        match sensitivity.analyze(engine.as_ref(), allvars, &bounds) {
+            Ok (indices) if indices.samples < 2 => println!("[ERROR] Too few samples could be analyzed"),
+            Ok (indices) => {
+                println!("{}", indices);
+                indices.plot_bars(&engine.name());
+            },
+            Err (error) => println!("[ERROR] {}", error),
+        }
+    } else if let Cli::Doe {
+        allvars,
+        engine,
+        experiment,
+        requirements,
+        bounds,
+        output,
+    } = cli {
+        println!("{} DESIGN OF EXPERIMENTS", engine.name().to_uppercase());
+        println!("====================\n");
+
+        let samples = match experiment.points(allvars, &bounds) {
+            Ok (samples) => samples,
+            Err (error) => {
+                println!("[ERROR] {}", error);
+                return;
+            },
+        };
+        let batch = Batch::evaluate(engine.as_ref(), &requirements, &experiment.inputs, &samples);
+        if let Err (error) = batch.write(Path::new(&output)) {
+            println!("[ERROR] {}", error);
+            return;
+        }
+
+        println!("Samples: {}", batch.rows.len());
+        println!("Failed samples: {}", batch.failed);
+        println!("Feasible samples: {}", batch.feasible);
+        println!("Results written to {}", output);
+    } else if let Cli::Batch {
+        engine,
+        inputs,
+        designs,
+        requirements,
+        output,
+    } = cli {
+        println!("{} BATCH EVALUATION", engine.name().to_uppercase());
+        println!("====================\n");
+
+        let batch = Batch::evaluate(engine.as_ref(), &requirements, &inputs, &designs);
+        if let Err (error) = batch.write(Path::new(&output)) {
+            println!("[ERROR] {}", error);
+            return;
+        }
+
+        println!("Designs: {}", batch.rows.len());
+        println!("Failed designs: {}", batch.failed);
+        println!("Feasible designs: {}", batch.feasible);
+        println!("Results written to {}", output);
     }
 }
\ No newline at end of file
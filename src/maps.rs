@@ -0,0 +1,266 @@
+//! Reads and scales tabular turbomachinery maps.
+//!
+//! A map is a text file of speed lines.  Lines starting with `#` are comments.
+//! The header gives the map speed and beta of the design point, and each speed
+//! line gives three rows of values at beta lines equally spaced from 0 to 1:
+//!
+//! ```text
+//! design_speed 1.0
+//! design_beta 0.5
+//!
+//! speed 0.9
+//! flow        0.80  0.82  0.84
+//! ratio       1.90  1.80  1.60
+//! efficiency  0.84  0.86  0.83
+//!
+//! speed 1.0
+//! ...
+//! ```
+//!
+//! `flow` is the corrected mass flow, `ratio` is the total pressure ratio of a
+//! compressor or the total expansion ratio of a turbine, and `efficiency` is the
+//! isentropic efficiency.  Speed lines must be given in increasing order of speed,
+//! and all rows must have the same number of beta lines.
+
+use std::{
+    fs,
+    path::Path,
+    process,
+    str::FromStr,
+};
+
+#[derive(Clone, Debug)]
+/// Holds a tabular turbomachinery map.
+pub struct Map {
+    /// Map speed of the design point
+    pub design_speed: f64,
+
+    /// Beta of the design point
+    pub design_beta: f64,
+
+    /// Corrected speeds of each speed line
+    pub speeds: Vec<f64>,
+
+    /// Corrected mass flow on each speed line
+    pub flow: Vec<Vec<f64>>,
+
+    /// Total pressure (or expansion) ratio on each speed line
+    pub ratio: Vec<Vec<f64>>,
+
+    /// Isentropic efficiency on each speed line
+    pub efficiency: Vec<Vec<f64>>,
+}
+
+impl From<&str> for Map {
+    fn from(text: &str) -> Self {
+        text.parse().unwrap_or_else(|error| {
+            println!("[FATAL] {}", error);
+            process::exit(0);
+        })
+    }
+}
+
+impl FromStr for Map {
+    type Err = String;
+
+    /// Parses a map written as speed lines.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut map = Map {
+            design_speed: 1.0,
+            design_beta: 0.5,
+            speeds: Vec::new(),
+            flow: Vec::new(),
+            ratio: Vec::new(),
+            efficiency: Vec::new(),
+        };
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let key = words.next().unwrap_or_default();
+            let values = words.map(|word| str::parse::<f64>(word)
+                .map_err(|_| format!("Could not parse {} as numeric value in map", word))
+            ).collect::<Result<Vec<f64>, String>>()?;
+
+            let single = || match values.as_slice() {
+                &[value] => Ok (value),
+                _ => Err (format!("Map entry {} expects one value", key)),
+            };
+
+            match key {
+                "design_speed" => map.design_speed = single()?,
+                "design_beta" => map.design_beta = single()?,
+                "speed" => map.speeds.push(single()?),
+                "flow" => map.flow.push(values),
+                "ratio" => map.ratio.push(values),
+                "efficiency" => map.efficiency.push(values),
+                _ => return Err (format!("Unrecognized map entry {}", key)),
+            }
+        }
+
+        let lines = map.speeds.len();
+        if lines < 2 || map.flow.len() != lines || map.ratio.len() != lines || map.efficiency.len() != lines {
+            return Err ("A map needs at least two speed lines, each with flow, ratio and efficiency rows".to_string());
+        }
+
+        // Lookups interpolate between neighbouring speed lines
+        if map.speeds.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err ("Map speed lines must be given in increasing order of speed".to_string());
+        }
+
+        // Every row must match the first before lookups index into it
+        let betas = map.flow.first().map_or(0, Vec::len);
+        if betas < 2 || [&map.flow, &map.ratio, &map.efficiency].iter().any(|rows| rows.iter().any(|row| row.len() != betas)) {
+            return Err ("All map rows need the same number (at least two) of beta lines".to_string());
+        }
+
+        Ok (map)
+    }
+}
+
+/// Finds the interval of a sorted table containing `x` and the fractional position of `x` within it.
+///
+/// Positions outside the table extrapolate from the first or last interval.
+fn locate(table: &[f64], x: f64) -> (usize, f64) {
+    let mut i = 0;
+    while i + 2 < table.len() && x > table[i + 1] {
+        i += 1;
+    }
+
+    (i, (x - table[i]) / (table[i + 1] - table[i]))
+}
+
+impl Map {
+    /// Reads a map from a file.
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok (text) => text.as_str().into(),
+            Err (_) => {
+                println!("[FATAL] Could not read map {}", path.display());
+                process::exit(0);
+            },
+        }
+    }
+
+    /// Interpolates corrected flow, ratio and efficiency at a given map speed and beta.
+    pub fn lookup(&self, speed: f64, beta: f64) -> (f64, f64, f64) {
+        let betas = self.flow[0].len();
+        let beta_table = (0..betas).map(|j| j as f64 / (betas - 1) as f64).collect::<Vec<f64>>();
+
+        let (i, s) = locate(&self.speeds, speed);
+        let (j, b) = locate(&beta_table, beta);
+
+        let interpolate = |rows: &Vec<Vec<f64>>| {
+            let low = rows[i][j] + b*(rows[i][j + 1] - rows[i][j]);
+            let high = rows[i + 1][j] + b*(rows[i + 1][j + 1] - rows[i + 1][j]);
+            low + s*(high - low)
+        };
+
+        (interpolate(&self.flow), interpolate(&self.ratio), interpolate(&self.efficiency))
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Holds a map scaled to the design point of one component.
+pub struct ScaledMap {
+    /// Unscaled map
+    pub map: Map,
+
+    /// Corrected flow at the design point
+    pub flow: f64,
+
+    /// Total pressure (or expansion) ratio at the design point
+    pub ratio: f64,
+
+    /// Isentropic efficiency at the design point
+    pub efficiency: f64,
+}
+
+impl ScaledMap {
+    /// Scales a map so that its design point matches the given design values.
+    pub fn new(map: &Map, flow: f64, ratio: f64, efficiency: f64) -> Self {
+        Self {
+            map: map.clone(),
+            flow,
+            ratio,
+            efficiency,
+        }
+    }
+
+    /// Interpolates corrected flow, ratio and efficiency at a relative corrected speed and beta.
+    ///
+    /// Flow and efficiency scale in proportion to their design values, and the ratio
+    /// scales so that `ratio - 1` is proportional to its design value.
+    pub fn lookup(&self, speed: f64, beta: f64) -> (f64, f64, f64) {
+        let (flow_d, ratio_d, efficiency_d) = self.map.lookup(self.map.design_speed, self.map.design_beta);
+        let (flow, ratio, efficiency) = self.map.lookup(speed*self.map.design_speed, beta);
+
+        (
+            flow * self.flow/flow_d,
+            1.0 + (ratio - 1.0) * (self.ratio - 1.0)/(ratio_d - 1.0),
+            efficiency * self.efficiency/efficiency_d,
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Holds the unscaled maps of each kind of turbomachine.
+pub struct Maps {
+    /// Fan map
+    pub fan: Map,
+
+    /// Compressor map, used for the booster and the HPC
+    pub compressor: Map,
+
+    /// Turbine map, used for the HPT and the LPT
+    pub turbine: Map,
+}
+
+impl Default for Maps {
+    /// Constructs the bundled generic maps.
+    fn default() -> Self {
+        Self {
+            fan: include_str!("maps/fan.map").into(),
+            compressor: include_str!("maps/compressor.map").into(),
+            turbine: include_str!("maps/turbine.map").into(),
+        }
+    }
+}
+
+impl Maps {
+    /// Reads `fan.map`, `compressor.map` and `turbine.map` from a directory.
+    pub fn load(directory: &Path) -> Self {
+        Self {
+            fan: Map::load(&directory.join("fan.map")),
+            compressor: Map::load(&directory.join("compressor.map")),
+            turbine: Map::load(&directory.join("turbine.map")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bundled_maps() {
+        for text in [include_str!("maps/fan.map"), include_str!("maps/compressor.map"), include_str!("maps/turbine.map")] {
+            let map: Map = text.parse().unwrap();
+
+            assert!(map.speeds.len() >= 2);
+            assert_eq!(map.flow.len(), map.speeds.len());
+        }
+    }
+
+    #[test]
+    fn rejects_bad_maps() {
+        let line = |speed: &str| format!("speed {}\nflow 1 2\nratio 1 2\nefficiency 1 2\n", speed);
+
+        let error = (line("1.0") + &line("0.9")).parse::<Map>().unwrap_err();
+        assert_eq!(error, "Map speed lines must be given in increasing order of speed");
+    }
+}
\ No newline at end of file
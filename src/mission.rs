@@ -0,0 +1,325 @@
+//! Flies an aircraft mission on a set of engines and reports its fuel burn.
+
+use std::fmt::{
+    self,
+    Display,
+    Formatter,
+};
+
+use crate::{
+    atmosphere::{
+        isa,
+        G0,
+    },
+    constants::*,
+    weight::station,
+    nox_index,
+    Condition,
+    Cycle,
+    Engine,
+    Maps,
+    Throttle,
+    Variables,
+};
+
+#[derive(Clone, Copy, Debug)]
+/// Describes an aircraft and the mission it flies.
+pub struct Mission {
+    /// Takeoff mass, including fuel (in kg)
+    pub takeoff_mass: f64,
+
+    /// Lift-to-drag ratio, taken as constant over the mission
+    pub lift_to_drag: f64,
+
+    /// Mission range, from takeoff to landing (in m)
+    pub range: f64,
+
+    /// Cruise altitude (in m)
+    pub cruise_altitude: f64,
+
+    /// Cruise Mach number
+    pub cruise_mach: f64,
+
+    /// Mean Mach number during climb and descent
+    pub climb_mach: f64,
+
+    /// Combustor exit temperature during climb, as a fraction of its design value
+    pub climb_throttle: f64,
+
+    /// Combustor exit temperature during taxi and descent, as a fraction of its design value
+    pub idle_throttle: f64,
+
+    /// Total taxi time, out and in (in s)
+    pub taxi_time: f64,
+
+    /// Takeoff time at full throttle (in s)
+    pub takeoff_time: f64,
+
+    /// Rate of descent (in m/s)
+    pub descent_rate: f64,
+
+    /// Contingency fuel, as a fraction of trip fuel
+    pub contingency: f64,
+
+    /// Holding altitude (in m)
+    pub holding_altitude: f64,
+
+    /// Holding Mach number
+    pub holding_mach: f64,
+
+    /// Holding time (in s)
+    pub holding_time: f64,
+
+    /// Number of aircraft engines
+    pub engine_count: usize,
+}
+
+impl Default for Mission {
+    /// Constructs a short-to-medium range mission for a twin-engine airliner.
+    fn default() -> Self {
+        Self {
+            takeoff_mass: 75_000.0,
+            lift_to_drag: 17.0,
+            range: 3_000_000.0,
+            cruise_altitude: 10_668.0,
+            cruise_mach: 0.78,
+            climb_mach: 0.6,
+            climb_throttle: 0.95,
+            idle_throttle: 0.6,
+            taxi_time: 900.0,
+            takeoff_time: 60.0,
+            descent_rate: 10.0,
+            contingency: 0.05,
+            holding_altitude: 450.0,
+            holding_mach: 0.35,
+            holding_time: 1800.0,
+            engine_count: ENGINE_COUNT,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the fuel burned, time and distance flown in one mission segment.
+pub struct Segment {
+    /// Segment name
+    pub name: &'static str,
+
+    /// Fuel burned by all engines (in kg)
+    pub fuel: f64,
+
+    /// NOx emitted by all engines (in kg)
+    pub nox: f64,
+
+    /// Duration (in s)
+    pub time: f64,
+
+    /// Ground distance covered (in m)
+    pub distance: f64,
+}
+
+#[derive(Clone, Debug)]
+/// Holds the results of a mission.
+pub struct Flight {
+    /// Fuel burned in each segment, from taxi out to taxi in
+    pub segments: Vec<Segment>,
+
+    /// Block fuel, burned from taxi out to taxi in (in kg)
+    pub block_fuel: f64,
+
+    /// Reserve fuel, carried but not burned (in kg)
+    pub reserve_fuel: f64,
+
+    /// Range flown (in m)
+    pub range: f64,
+
+    /// Landing mass (in kg)
+    pub landing_mass: f64,
+
+    /// NOx emitted from taxi out to taxi in (in kg)
+    pub nox: f64,
+
+    /// CO2 emitted from taxi out to taxi in (in kg)
+    pub co2: f64,
+
+    /// Net lifecycle CO2 of the block fuel (in kg)
+    pub net_co2: f64,
+
+    /// H2O emitted from taxi out to taxi in (in kg)
+    pub h2o: f64,
+}
+
+impl Display for Flight {
+    /// Format this data structure.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+
+        output.push_str(&format!("{:<10}{:>16}{:>16}{:>16}{:>16}\n", "Segment", "Fuel (kg)", "NOx (kg)", "Time (min)", "Distance (km)"));
+        for segment in &self.segments {
+            output.push_str(&format!("{:<10}{:>16.1}{:>16.3}{:>16.1}{:>16.1}\n", segment.name, segment.fuel, segment.nox, segment.time/60.0, segment.distance/1000.0));
+        }
+
+        output.push_str(&format!("\nBlock fuel: {:.1} kg\n", self.block_fuel));
+        output.push_str(&format!("Reserve fuel: {:.1} kg\n", self.reserve_fuel));
+        output.push_str(&format!("Range: {:.1} km\n", self.range/1000.0));
+        output.push_str(&format!("Landing mass: {:.1} kg\n", self.landing_mass));
+        output.push_str(&format!("\nNOx: {:.3} kg\n", self.nox));
+        output.push_str(&format!("CO2: {:.1} kg\n", self.co2));
+        output.push_str(&format!("Net lifecycle CO2: {:.1} kg\n", self.net_co2));
+        output.push_str(&format!("H2O: {:.1} kg\n", self.h2o));
+
+        write!(f, "{}", output)
+    }
+}
+
+/// Computes the NOx emissions index (in g/kg of fuel) of a cycle, or zero if the engine has no combustor inlet.
+fn cycle_nox_index(cycle: &Cycle) -> f64 {
+    station(cycle, "3").map_or(0.0, |(t_t3, p_t3)| nox_index(t_t3, p_t3))
+}
+
+/// Computes the true airspeed (in m/s) at a given altitude and Mach number.
+fn airspeed(altitude: f64, mach: f64) -> f64 {
+    mach * (GAMMA * R * isa(altitude).0).sqrt()
+}
+
+impl Mission {
+    /// Runs one engine off-design at a fraction of its design combustor exit temperature.
+    fn run<E: Engine + ?Sized>(&self, engine: &E, variables: Variables, maps: &Maps, altitude: f64, mach: f64, throttle: f64) -> Option<Cycle> {
+        engine.off_design(variables, maps, Condition {
+            altitude,
+            mach,
+            throttle: Throttle::Temperature (throttle * variables.hpt_inlet_temp),
+        })
+    }
+
+    /// Finds the throttle setting at which one engine gives a required thrust (in N).
+    fn match_thrust<E: Engine + ?Sized>(&self, engine: &E, variables: Variables, maps: &Maps, altitude: f64, mach: f64, thrust: f64) -> Option<Cycle> {
+        // Secant iteration on the throttle setting
+        let (mut x0, mut x1) = (0.8, 0.9);
+        let mut r0 = self.run(engine, variables, maps, altitude, mach, x0)?.thrust - thrust;
+        for _ in 0..30 {
+            let cycle = self.run(engine, variables, maps, altitude, mach, x1)?;
+            let r1 = cycle.thrust - thrust;
+            if (r1 / thrust).abs() < 1.0E-6 {
+                return Some (cycle);
+            }
+
+            let x2 = (x1 - r1*(x1 - x0)/(r1 - r0)).clamp(0.5, 1.2);
+            (x0, r0, x1) = (x1, r1, x2);
+        }
+
+        None
+    }
+
+    /// Flies this mission on `engine_count` engines sized by `variables`.
+    ///
+    /// Climb uses the energy method at the mean climb altitude, cruise and holding
+    /// use the Breguet range and endurance equations with the engines throttled to
+    /// match drag, and taxi, takeoff and descent burn fuel at fixed throttle settings.
+    /// Returns `None` if the engines cannot fly the mission.
+    pub fn fly<E: Engine + ?Sized>(&self, engine: &E, variables: Variables, maps: &Maps) -> Option<Flight> {
+        let n = self.engine_count as f64;
+        let mut mass = self.takeoff_mass;
+        let mut segments = Vec::new();
+
+        // Taxi, at sea level static idle
+        let idle = self.run(engine, variables, maps, 0.0, 0.0, self.idle_throttle)?;
+        let taxi = n * idle.fuel_flow * self.taxi_time;
+
+        // Takeoff, at sea level static full throttle
+        let takeoff = self.run(engine, variables, maps, 0.0, 0.0, 1.0)?;
+        let fuel = n * takeoff.fuel_flow * self.takeoff_time;
+        segments.push(Segment {
+            name: "Takeoff",
+            fuel,
+            nox: fuel * cycle_nox_index(&takeoff)/1000.0,
+            time: self.takeoff_time,
+            distance: 0.0,
+        });
+        mass -= 0.5*taxi + segments[0].fuel;
+
+        // Climb, by the energy method at the mean climb altitude
+        let h = 0.5 * self.cruise_altitude;
+        let v = airspeed(h, self.climb_mach);
+        let climb = self.run(engine, variables, maps, h, self.climb_mach, self.climb_throttle)?;
+        let excess = n*climb.thrust - mass*G0/self.lift_to_drag;
+        if excess <= 0.0 {
+            return None;
+        }
+        let v_cruise = airspeed(self.cruise_altitude, self.cruise_mach);
+        let energy = self.cruise_altitude + v_cruise.powi(2)/(2.0*G0);
+        let time = mass*G0*energy / (excess*v);
+        let fuel = n * climb.fuel_flow * time;
+        segments.push(Segment {
+            name: "Climb",
+            fuel,
+            nox: fuel * cycle_nox_index(&climb)/1000.0,
+            time,
+            distance: v*time,
+        });
+        mass -= segments[1].fuel;
+
+        // Descent, at idle at the mean descent altitude
+        let descent = self.run(engine, variables, maps, h, self.climb_mach, self.idle_throttle)?;
+        let time = self.cruise_altitude / self.descent_rate;
+        let fuel = n * descent.fuel_flow * time;
+        let descent = Segment {
+            name: "Descent",
+            fuel,
+            nox: fuel * cycle_nox_index(&descent)/1000.0,
+            time,
+            distance: v*time,
+        };
+
+        // Cruise, by the Breguet range equation with thrust matched to drag at mid-cruise
+        let distance = (self.range - segments[1].distance - descent.distance).max(0.0);
+        let mut mid = mass;
+        let mut burn = 0.0;
+        let mut index = 0.0;
+        for _ in 0..3 {
+            let cycle = self.match_thrust(engine, variables, maps, self.cruise_altitude, self.cruise_mach, mid*G0/self.lift_to_drag/n)?;
+            let c = G0 * cycle.sfc/3600.0;
+            burn = mass * (1.0 - (-distance*c / (v_cruise*self.lift_to_drag)).exp());
+            mid = mass - 0.5*burn;
+            index = cycle_nox_index(&cycle);
+        }
+        segments.push(Segment {
+            name: "Cruise",
+            fuel: burn,
+            nox: burn * index/1000.0,
+            time: distance / v_cruise,
+            distance,
+        });
+        mass -= burn + descent.fuel;
+        segments.push(descent);
+
+        segments.insert(0, Segment {
+            name: "Taxi",
+            fuel: taxi,
+            nox: taxi * cycle_nox_index(&idle)/1000.0,
+            time: self.taxi_time,
+            distance: 0.0,
+        });
+        mass -= 0.5*taxi;
+
+        // Reserves: contingency on trip fuel, and holding by the Breguet endurance equation
+        let trip = segments.iter().filter(|s| s.name != "Taxi").map(|s| s.fuel).sum::<f64>();
+        let hold = self.match_thrust(engine, variables, maps, self.holding_altitude, self.holding_mach, mass*G0/self.lift_to_drag/n)?;
+        let c = G0 * hold.sfc/3600.0;
+        let holding = mass * (1.0 - (-self.holding_time*c / self.lift_to_drag).exp());
+
+        let block_fuel = segments.iter().map(|s| s.fuel).sum::<f64>();
+        let fuel = engine.fuel();
+
+        Some (Flight {
+            block_fuel,
+            reserve_fuel: self.contingency*trip + holding,
+            range: segments.iter().map(|s| s.distance).sum(),
+            landing_mass: mass,
+            nox: segments.iter().map(|s| s.nox).sum(),
+            co2: block_fuel * fuel.ei_co2()/1000.0,
+            net_co2: block_fuel * fuel.ei_co2()*fuel.lifecycle_co2/1000.0,
+            h2o: block_fuel * fuel.ei_h2o()/1000.0,
+            segments,
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,193 @@
+//! Estimates nacelle geometry and drag, and the installed performance of an engine.
+//!
+//! Nacelle drag is built up from flat-plate turbulent skin friction on the wetted
+//! area of the fan cowl and the core cowl, with a form factor for the fan cowl
+//! fineness ratio and an interference factor for the pylon.  Spillage and
+//! afterbody drag are neglected.
+
+use std::fmt::{
+    self,
+    Display,
+    Formatter,
+};
+
+use crate::{
+    atmosphere::isa,
+    constants::*,
+    Condition,
+    Engine,
+    Maps,
+    Size,
+    Variables,
+};
+
+/// Ratio of highlight (inlet lip) diameter to fan diameter.
+const HIGHLIGHT_RATIO: f64 = 1.05;
+
+/// Ratio of maximum nacelle diameter to fan diameter.
+const MAX_DIAMETER_RATIO: f64 = 1.2;
+
+/// Inlet length from highlight to fan face, per fan diameter.
+const INLET_LENGTH_RATIO: f64 = 0.6;
+
+/// Short fan cowl length from highlight to bypass nozzle, per fan diameter.
+const FAN_COWL_LENGTH_RATIO: f64 = 1.4;
+
+/// Ratio of core cowl diameter to the largest core casing diameter.
+const CORE_COWL_RATIO: f64 = 1.1;
+
+/// Interference factor for a pylon-mounted nacelle.
+const INTERFERENCE_FACTOR: f64 = 1.3;
+
+/// Sutherland reference viscosity (in Pa-s) at `MU_REF_TEMP`.
+const MU_REF: f64 = 1.716E-5;
+
+/// Sutherland reference temperature (in K).
+const MU_REF_TEMP: f64 = 273.15;
+
+/// Sutherland constant for air (in K).
+const SUTHERLAND: f64 = 110.4;
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the estimated geometry of a nacelle.
+pub struct Nacelle {
+    /// Highlight (inlet lip) diameter (in m)
+    pub highlight_diameter: f64,
+
+    /// Maximum diameter (in m)
+    pub max_diameter: f64,
+
+    /// Fan cowl length, from highlight to nozzle exit (in m)
+    pub length: f64,
+
+    /// Core cowl diameter (in m), zero if the core is fully cowled
+    pub core_diameter: f64,
+
+    /// Exposed core cowl length (in m)
+    pub core_length: f64,
+}
+
+/// Computes the turbulent flat-plate skin friction coefficient at a Reynolds and Mach number.
+fn skin_friction(reynolds: f64, mach: f64) -> f64 {
+    0.455 / (reynolds.log10().powf(2.58) * (1.0 + 0.144*mach.powi(2)).powf(0.65))
+}
+
+impl Nacelle {
+    /// Estimates a short fan cowl and exposed core cowl for a separate exhaust engine.
+    pub fn separate(fan_diameter: f64, size: &Size) -> Self {
+        let core = size.modules.iter()
+            .filter(|m| m.name != "Fan")
+            .map(|m| m.diameter)
+            .fold(0.0, f64::max);
+        let length = FAN_COWL_LENGTH_RATIO * fan_diameter;
+
+        Self {
+            highlight_diameter: HIGHLIGHT_RATIO * fan_diameter,
+            max_diameter: MAX_DIAMETER_RATIO * fan_diameter,
+            length,
+            core_diameter: CORE_COWL_RATIO * core,
+            core_length: (INLET_LENGTH_RATIO * fan_diameter + size.length - length).max(0.0),
+        }
+    }
+
+    /// Computes the wetted area (in m2) of the fan cowl and the core cowl.
+    pub fn wetted_area(&self) -> (f64, f64) {
+        (
+            PI * 0.5*(self.highlight_diameter + self.max_diameter) * self.length,
+            PI * self.core_diameter * self.core_length,
+        )
+    }
+
+    /// Computes the drag (in N) of this nacelle at an ISA altitude (in m) and flight Mach number.
+    pub fn drag(&self, altitude: f64, mach: f64) -> f64 {
+        if mach <= 0.0 {
+            return 0.0;
+        }
+
+        let (t, p) = isa(altitude);
+        let rho = p / (R * t);
+        let v = mach * (GAMMA * R * t).sqrt();
+        let mu = MU_REF * (t / MU_REF_TEMP).powf(1.5) * (MU_REF_TEMP + SUTHERLAND) / (t + SUTHERLAND);
+        let q = 0.5 * rho * v.powi(2);
+
+        let (fan_area, core_area) = self.wetted_area();
+        let form_factor = 1.0 + 0.35 / (self.length / self.max_diameter);
+        let fan = skin_friction(rho * v * self.length / mu, mach) * form_factor * fan_area;
+        let core = if self.core_length > 0.0 {
+            skin_friction(rho * v * self.core_length / mu, mach) * core_area
+        } else {
+            0.0
+        };
+
+        q * INTERFERENCE_FACTOR * (fan + core)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the installed and uninstalled performance of an engine at a flight condition.
+pub struct Installed {
+    /// Uninstalled thrust (in N) and SFC (in kg/N-hr) at the design point
+    pub design: (f64, f64),
+
+    /// Uninstalled thrust (in N) and SFC (in kg/N-hr) at the flight condition
+    pub uninstalled: (f64, f64),
+
+    /// Nacelle drag (in N)
+    pub drag: f64,
+
+    /// Installed net thrust (in N)
+    pub thrust: f64,
+
+    /// Installed SFC (in kg/N-hr)
+    pub sfc: f64,
+
+    /// Nacelle geometry
+    pub nacelle: Nacelle,
+}
+
+impl Installed {
+    /// Computes the installed performance of an engine, sized by `variables`, at a flight condition.
+    ///
+    /// Returns `None` if the engine has no nacelle model or cannot be matched at this condition.
+    pub fn new(engine: &dyn Engine, variables: Variables, maps: &Maps, condition: Condition) -> Option<Self> {
+        let nacelle = engine.nacelle(variables)?;
+        let cycle = engine.off_design(variables, maps, condition)?;
+        let drag = nacelle.drag(condition.altitude, condition.mach);
+        let thrust = cycle.thrust - drag;
+
+        Some (Self {
+            design: engine.analyze(variables),
+            uninstalled: (cycle.thrust, cycle.sfc),
+            drag,
+            thrust,
+            sfc: cycle.fuel_flow / thrust * 3600.0,
+            nacelle,
+        })
+    }
+}
+
+impl Display for Installed {
+    /// Format this data structure.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+
+        output.push_str(&format!("{:<24}{:>16}{:>16}\n", "", "Thrust (N)", "SFC (kg/N-hr)"));
+        output.push_str(&format!("{:<24}{:>16.1}{:>16.6}\n", "Design (uninstalled)", self.design.0, self.design.1));
+        output.push_str(&format!("{:<24}{:>16.1}{:>16.6}\n", "Uninstalled", self.uninstalled.0, self.uninstalled.1));
+        output.push_str(&format!("{:<24}{:>16.1}{:>16.6}\n", "Installed", self.thrust, self.sfc));
+
+        let (fan_area, core_area) = self.nacelle.wetted_area();
+        output.push_str(&format!("\nNacelle drag: {:.1} N\n", self.drag));
+        output.push_str(&format!("Highlight diameter: {:.3} m\n", self.nacelle.highlight_diameter));
+        output.push_str(&format!("Maximum diameter: {:.3} m\n", self.nacelle.max_diameter));
+        output.push_str(&format!("Fan cowl length: {:.3} m\n", self.nacelle.length));
+        output.push_str(&format!("Fan cowl wetted area: {:.3} m2\n", fan_area));
+        if self.nacelle.core_length > 0.0 {
+            output.push_str(&format!("Core cowl diameter: {:.3} m\n", self.nacelle.core_diameter));
+            output.push_str(&format!("Core cowl length: {:.3} m\n", self.nacelle.core_length));
+            output.push_str(&format!("Core cowl wetted area: {:.3} m2\n", core_area));
+        }
+
+        write!(f, "{}", output)
+    }
+}
\ No newline at end of file
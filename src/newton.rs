@@ -0,0 +1,83 @@
+//! Solves systems of nonlinear equations by the Newton-Raphson method.
+
+/// Relative perturbation used for finite-difference Jacobians.
+const PERTURBATION: f64 = 1.0E-6;
+
+/// Largest change in any unknown allowed in one iteration.
+const MAX_STEP: f64 = 0.1;
+
+/// Solves the linear system `a x = b` by Gaussian elimination with partial pivoting.
+///
+/// Returns `None` if the system is singular.
+fn gauss(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for k in 0..n {
+        let pivot = (k..n).max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs()))?;
+        if a[pivot][k].abs() < 1.0E-14 {
+            return None;
+        }
+        a.swap(k, pivot);
+        b.swap(k, pivot);
+
+        let (upper, lower) = a.split_at_mut(k + 1);
+        let row = &upper[k];
+        for (i, other) in lower.iter_mut().enumerate() {
+            let factor = other[k] / row[k];
+            for (x, y) in other[k..].iter_mut().zip(&row[k..]) {
+                *x -= factor * y;
+            }
+            b[k + 1 + i] -= factor * b[k];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for k in (0..n).rev() {
+        let sum: f64 = ((k + 1)..n).map(|j| a[k][j] * x[j]).sum();
+        x[k] = (b[k] - sum) / a[k][k];
+    }
+
+    Some (x)
+}
+
+/// Finds `x` such that every residual of `f(x)` is within `tolerance` of zero, starting from `x`.
+///
+/// The Jacobian is computed by forward differences and each step is limited to
+/// `MAX_STEP` in every unknown.  Returns `None` if the iteration fails to converge
+/// within `iterations` steps.
+pub fn solve<F: Fn(&[f64]) -> Vec<f64>>(f: F, mut x: Vec<f64>, tolerance: f64, iterations: usize) -> Option<Vec<f64>> {
+    for _ in 0..iterations {
+        let r = f(&x);
+        if r.iter().any(|r| !r.is_finite()) {
+            return None;
+        }
+        if r.iter().all(|r| r.abs() < tolerance) {
+            return Some (x);
+        }
+
+        // Finite-difference Jacobian, stored by rows
+        let n = x.len();
+        let mut jacobian = vec![vec![0.0; n]; n];
+        for j in 0..n {
+            let mut x_j = x.clone();
+            let h = PERTURBATION * x[j].abs().max(1.0);
+            x_j[j] += h;
+            for (row, (r_j, r)) in jacobian.iter_mut().zip(f(&x_j).iter().zip(&r)) {
+                row[j] = (r_j - r) / h;
+            }
+        }
+
+        let step = gauss(jacobian, r.iter().map(|r| -r).collect())?;
+        let largest = step.iter().fold(0.0, |m: f64, s| m.max(s.abs()));
+        let scale = if largest > MAX_STEP {
+            MAX_STEP / largest
+        } else {
+            1.0
+        };
+
+        for (x, step) in x.iter_mut().zip(&step) {
+            *x += scale * step;
+        }
+    }
+
+    None
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+//! Selects the quantity minimized by the optimizer.
+//!
+//! Every objective is minimized subject to the same thrust requirement and
+//! design limits.  Objectives other than SFC are scaled so that their gradients
+//! are of the same order as that of SFC, which lets the optimizer use a single
+//! step size.
+
+use crate::{
+    Engine,
+    Maps,
+    Mission,
+    Noise,
+    Variables,
+};
+
+#[derive(Clone, Debug)]
+/// Enumerates the objectives the optimizer may minimize.
+pub enum Objective {
+    /// Design-point specific fuel consumption
+    Sfc,
+
+    /// Block fuel of a mission, as a fraction of takeoff mass
+    Fuel {
+        mission: Mission,
+        maps: Box<Maps>,
+    },
+
+    /// Dry weight of each engine (in t)
+    Weight,
+
+    /// Weighted sum of SFC and jet noise
+    ///
+    /// Both are normalized by their values at the starting design, and the sum is
+    /// rescaled by the starting SFC.  A weight of 1 minimizes jet noise alone;
+    /// sweeping the weight from 0 to 1 traces the Pareto front between SFC and
+    /// noise.
+    Noise {
+        /// Weight of jet noise, from 0 (SFC only) to 1 (noise only)
+        weight: f64,
+
+        /// SFC (in kg/N-hr) and jet noise (in dB) of the starting design
+        reference: (f64, f64),
+    },
+}
+
+impl Objective {
+    /// Weighs jet noise against SFC, taking the reference SFC and jet noise from a starting design.
+    pub fn noise<E: Engine + ?Sized>(engine: &E, weight: f64, variables: Variables) -> Self {
+        let sfc = engine.analyze(variables).1;
+        let noise = Noise::new(&engine.cycle(variables)).map_or(f64::NAN, |noise| noise.level);
+
+        Objective::Noise {
+            weight,
+            reference: (sfc, noise),
+        }
+    }
+
+    /// Computes the design-point thrust and the value of this objective.
+    pub fn evaluate<E: Engine + ?Sized>(&self, engine: &E, variables: Variables) -> (f64, f64) {
+        match self {
+            Objective::Sfc => engine.analyze(variables),
+            Objective::Fuel { mission, maps } => {
+                let fuel = mission.fly(engine, variables, maps)
+                    .map_or(f64::NAN, |flight| flight.block_fuel / mission.takeoff_mass);
+
+                (engine.analyze(variables).0, fuel)
+            },
+            Objective::Weight => {
+                let weight = engine.size(variables).map_or(f64::NAN, |size| size.weight / 1000.0);
+
+                (engine.analyze(variables).0, weight)
+            },
+            Objective::Noise { weight, reference: (sfc_ref, noise_ref) } => {
+                let cycle = engine.cycle(variables);
+                let (thrust, sfc) = engine.analyze(variables);
+                let noise = Noise::new(&cycle).map_or(f64::NAN, |noise| noise.level);
+
+                (thrust, sfc_ref * ((1.0 - weight) * sfc / sfc_ref + weight * noise / noise_ref))
+            },
+        }
+    }
+
+    /// Labels this objective.
+    pub fn label<E: Engine + ?Sized>(&self, engine: &E) -> &'static str {
+        match self {
+            Objective::Sfc => engine.sfc_label(),
+            Objective::Fuel { .. } => "Block Fuel Fraction",
+            Objective::Weight => "Dry Weight (t)",
+            Objective::Noise { .. } => "SFC-Noise Objective",
+        }
+    }
+}
\ No newline at end of file
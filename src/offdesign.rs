@@ -0,0 +1,466 @@
+//! Computes the off-design performance of a sized turbofan from component maps.
+
+use crate::{
+    atmosphere::isa,
+    constants::*,
+    flow::{
+        expansion_mach,
+        flow_area,
+        inlet_recovery,
+        mass_flow_parameter,
+        total_pressure_ratio,
+        total_temperature_ratio,
+        Stream,
+    },
+    gas::Gas,
+    maps::{
+        Maps,
+        ScaledMap,
+    },
+    newton,
+    turbofan::{
+        BleedStation,
+        Exhaust,
+        Spools,
+    },
+    Cycle,
+    Engine,
+    Turbofan,
+    Variables,
+};
+
+/// Convergence tolerance on the normalized matching residuals.
+const TOLERANCE: f64 = 1.0E-9;
+
+/// Maximum number of Newton-Raphson iterations.
+const ITERATIONS: usize = 200;
+
+#[derive(Clone, Copy, Debug)]
+/// Selects how the engine is throttled.
+pub enum Throttle {
+    /// Combustor exit temperature (in K)
+    Temperature (f64),
+
+    /// Fuel flow rate (in kg/s)
+    FuelFlow (f64),
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Holds a flight condition and throttle setting.
+pub struct Condition {
+    /// Altitude in the International Standard Atmosphere (in m)
+    pub altitude: f64,
+
+    /// Flight Mach number
+    pub mach: f64,
+
+    /// Throttle setting
+    pub throttle: Throttle,
+}
+
+/// Holds the total temperature and pressure at a station of the design point.
+#[derive(Clone, Copy, Debug)]
+struct Reference {
+    t_t: f64,
+    p_t: f64,
+}
+
+impl Reference {
+    /// Reads a station of a design cycle.
+    fn new(cycle: &Cycle, name: &str) -> Result<Self, String> {
+        let station = cycle.stations.iter().find(|s| s.name == name)
+            .ok_or_else(|| format!("The design cycle has no station {}", name))?;
+
+        Ok (Self {
+            t_t: station.total_temp,
+            p_t: station.total_pressure,
+        })
+    }
+
+    /// Computes the ratio of corrected to actual quantities relative to this reference.
+    fn theta(&self, t_t: f64) -> f64 {
+        (t_t / self.t_t).sqrt()
+    }
+
+    /// Computes the actual mass flow from a corrected mass flow referred to this reference.
+    fn flow(&self, corrected: f64, t_t: f64, p_t: f64) -> f64 {
+        corrected * (p_t / self.p_t) / self.theta(t_t)
+    }
+}
+
+/// Matches a sized two-spool turbofan with separate exhausts to its component maps.
+///
+/// The fan, booster and LPT share the low spool and the HPC and HPT share the high
+/// spool; with a geared fan the gear ratio is fixed, so all low-spool components run
+/// at the same speed relative to the design point.  The unknowns are the two spool
+/// speeds, the beta of each map and, when the fuel flow is given, the combustor exit
+/// temperature.  They are found by Newton-Raphson iteration on the flow continuity of
+/// the HPC, both turbines and both nozzles, and on the work balance of both spools.
+///
+/// Cooling and customer bleed fractions and shaft power offtakes are held at their
+/// design values.  Both nozzles are convergent with throat areas fixed at the design
+/// point, and thrust is computed from fully expanded jets as at the design point.
+pub struct OffDesign {
+    engine: Turbofan,
+    variables: Variables,
+    gas: Gas,
+
+    /// Scaled maps
+    fan: ScaledMap,
+    lpc: ScaledMap,
+    hpc: ScaledMap,
+    hpt: ScaledMap,
+    lpt: ScaledMap,
+
+    /// Design inlet conditions of each component
+    inlet_fan: Reference,
+    inlet_lpc: Reference,
+    inlet_hpc: Reference,
+    inlet_hpt: Reference,
+    inlet_lpt: Reference,
+
+    /// Design cooling air fractions of HPC exit flow
+    b_ngv: f64,
+    b_rotor: f64,
+
+    /// Design combustor exit temperature (in K)
+    t_t4: f64,
+
+    /// Design flow rates through the HPT, LPT and bypass nozzle, and design fuel flow (in kg/s)
+    w_hpt: f64,
+    w_lpt: f64,
+    w_bypass: f64,
+    w_fuel: f64,
+
+    /// Design HPT and LPT power (in W)
+    power_hpt: f64,
+    power_lpt: f64,
+
+    /// Nozzle throat areas (in m2)
+    a_9: f64,
+    a_19: f64,
+}
+
+impl OffDesign {
+    /// Checks that a turbofan can be matched off-design.
+    ///
+    /// Returns the reason if it cannot.
+    pub fn check(engine: Turbofan) -> Result<(), String> {
+        if !matches!(engine.spools, Spools::Two) || !matches!(engine.exhaust, Exhaust::Separate) || engine.afterburner {
+            return Err ("Off-design analysis supports two-spool turbofans with separate exhausts and no afterburner".to_string());
+        }
+
+        Ok (())
+    }
+
+    /// Sizes a turbofan at its design point and scales the maps to it.
+    ///
+    /// Returns the reason if the turbofan cannot be matched off-design.
+    pub fn new(engine: Turbofan, variables: Variables, maps: &Maps) -> Result<Self, String> {
+        Self::check(engine)?;
+
+        let gas = Gas::new(engine.gas, engine.fuel, variables);
+        let design = engine.cycle(variables);
+
+        let efficiency = |name: &str| design.efficiencies.iter().find(|e| e.name == name).map_or(1.0, |e| e.isentropic);
+
+        let inlet_fan = Reference::new(&design, "2")?;
+        let inlet_lpc = Reference::new(&design, "23")?;
+        let inlet_hpc = Reference::new(&design, if engine.booster { "25" } else { "23" })?;
+        let exit_hpc = Reference::new(&design, "3")?;
+        let t_t4 = variables.hpt_inlet_temp;
+        let inlet_hpt = Reference::new(&design, if design.stations.iter().any(|s| s.name == "41") { "41" } else { "4" })?;
+        let inlet_lpt = Reference::new(&design, "45")?;
+        let exit_lpt = Reference::new(&design, "5")?;
+
+        // Design flow rates
+        let beta = variables.fan_bypass;
+        let w_fan = design.mass_flow;
+        let w_core = w_fan / (1.0 + beta);
+        let m3 = 1.0 - variables.customer_bleed_fraction;
+        let m_hpc = Self::hpc_flow_fraction(engine, variables);
+        let f_burner = gas.burner_fuel(exit_hpc.t_t, t_t4, 0.0, 1.0, variables.fuel_delta_h);
+        let (b_ngv, b_rotor) = engine.cooling_fractions(&gas, variables, exit_hpc.t_t, t_t4, f_burner);
+        let w_fuel = design.fuel_flow;
+        let w_hpt = w_core*m3*(1.0 - b_rotor) + w_fuel;
+        let w_lpt = w_core*m3 + w_fuel;
+        let w_bypass = w_fan - w_core;
+        let far = w_fuel / (w_core*m3);
+
+        // Design turbine power
+        let far_41 = w_fuel / (w_hpt - w_fuel);
+        let t_t44 = gas.turbine_exit_temp(inlet_hpt.t_t, inlet_lpt.p_t/inlet_hpt.p_t, efficiency("HPT"), far_41);
+        let power_hpt = w_hpt * (gas.enthalpy(inlet_hpt.t_t, far_41) - gas.enthalpy(t_t44, far_41));
+        let power_lpt = w_lpt * (gas.enthalpy(inlet_lpt.t_t, far) - gas.enthalpy(exit_lpt.t_t, far));
+
+        let fan = ScaledMap::new(&maps.fan, w_fan, variables.fan_pressure_ratio, efficiency("Fan"));
+        let lpc = ScaledMap::new(&maps.compressor, w_core, variables.lpc_pressure_ratio, efficiency("LPC"));
+        let hpc = ScaledMap::new(&maps.compressor, w_core*m_hpc, variables.hpc_pressure_ratio, efficiency("HPC"));
+        let hpt = ScaledMap::new(&maps.turbine, w_hpt, inlet_hpt.p_t/inlet_lpt.p_t, efficiency("HPT"));
+        let lpt = ScaledMap::new(&maps.turbine, w_lpt, inlet_lpt.p_t/exit_lpt.p_t, efficiency("LPT"));
+
+        // Nozzle throats
+        let a_9 = Self::throat_area(gas.stream(w_lpt, exit_lpt.t_t, exit_lpt.p_t, far), P0);
+        let a_19 = Self::throat_area(gas.stream(w_bypass, inlet_lpc.t_t, inlet_lpc.p_t, 0.0), P0);
+
+        Ok (Self {
+            engine,
+            variables,
+            gas,
+            fan,
+            lpc,
+            hpc,
+            hpt,
+            lpt,
+            inlet_fan,
+            inlet_lpc,
+            inlet_hpc,
+            inlet_hpt,
+            inlet_lpt,
+            b_ngv,
+            b_rotor,
+            t_t4,
+            w_hpt,
+            w_lpt,
+            w_bypass,
+            w_fuel,
+            power_hpt,
+            power_lpt,
+            a_9,
+            a_19,
+        })
+    }
+
+    /// Gives the HPC map scaled to the design point.
+    pub fn hpc_map(&self) -> ScaledMap {
+        self.hpc.clone()
+    }
+
+    /// Computes the fraction of core air flow passing through the HPC.
+    fn hpc_flow_fraction(engine: Turbofan, variables: Variables) -> f64 {
+        match engine.bleed_station {
+            BleedStation::Hpc => 1.0,
+            _ => 1.0 - variables.customer_bleed_fraction,
+        }
+    }
+
+    /// Computes the Mach number at the throat of a convergent nozzle exhausting to a static pressure `p`.
+    fn throat_mach(stream: Stream, p: f64) -> f64 {
+        expansion_mach((stream.p_t / p).max(1.0), stream.gamma).min(1.0)
+    }
+
+    /// Computes the throat area (in m2) of a convergent nozzle passing a stream.
+    fn throat_area(stream: Stream, p: f64) -> f64 {
+        flow_area(stream.mdot, stream.t_t, stream.p_t, Self::throat_mach(stream, p), stream.gamma)
+    }
+
+    /// Computes the mass flow rate (in kg/s) passed by a nozzle throat.
+    fn nozzle_flow(stream: Stream, area: f64, p: f64) -> f64 {
+        area * stream.p_t * mass_flow_parameter(Self::throat_mach(stream, p), stream.gamma) / stream.t_t.sqrt()
+    }
+
+    /// Builds the initial guess of the unknowns at the design point.
+    fn initial_guess(&self, condition: Condition) -> Vec<f64> {
+        let mut x = vec![
+            1.0,
+            1.0,
+            self.fan.map.design_beta,
+            self.hpc.map.design_beta,
+            self.hpt.map.design_beta,
+            self.lpt.map.design_beta,
+        ];
+        if self.engine.booster {
+            x.push(self.lpc.map.design_beta);
+        }
+        if let Throttle::FuelFlow (_) = condition.throttle {
+            x.push(1.0);
+        }
+
+        x
+    }
+
+    /// Evaluates the engine at a set of unknowns.
+    ///
+    /// Returns the matching residuals and the resulting cycle.
+    fn evaluate(&self, x: &[f64], condition: Condition) -> (Vec<f64>, Cycle) {
+        let gas = &self.gas;
+        let variables = self.variables;
+        let mut cycle = Cycle::default();
+        let mut residuals = Vec::new();
+
+        let (n_l, n_h) = (x[0], x[1]);
+        let (beta_fan, beta_hpc, beta_hpt, beta_lpt) = (x[2], x[3], x[4], x[5]);
+        let mut next = 6;
+
+        // Free stream and inlet
+        let (t_amb, p_amb) = isa(condition.altitude);
+        let m0 = condition.mach;
+        let v0 = m0 * (GAMMA * R * t_amb).sqrt();
+        let t_t2 = t_amb * total_temperature_ratio(m0, GAMMA);
+        let p_t2 = p_amb * total_pressure_ratio(m0, GAMMA) * inlet_recovery(m0);
+
+        // Fan
+        let speed = n_l / self.inlet_fan.theta(t_t2);
+        let (flow, pi, eta) = self.fan.lookup(speed, beta_fan);
+        let w_fan = self.inlet_fan.flow(flow, t_t2, p_t2);
+        let t_t23 = gas.compressor_exit_temp(t_t2, pi, eta);
+        let p_t23 = pi*p_t2;
+        cycle.map_point("Fan", speed, beta_fan, flow, pi, eta);
+
+        // Low pressure compressor (booster)
+        let (t_t25, p_t25, w_core) = if self.engine.booster {
+            let beta_lpc = x[next];
+            next += 1;
+            let speed = n_l / self.inlet_lpc.theta(t_t23);
+            let (flow, pi, eta) = self.lpc.lookup(speed, beta_lpc);
+            cycle.map_point("LPC", speed, beta_lpc, flow, pi, eta);
+            (gas.compressor_exit_temp(t_t23, pi, eta), pi*p_t23, Some (self.inlet_lpc.flow(flow, t_t23, p_t23)))
+        } else {
+            (t_t23, p_t23, None)
+        };
+
+        // High pressure compressor
+        let m_hpc = Self::hpc_flow_fraction(self.engine, variables);
+        let speed = n_h / self.inlet_hpc.theta(t_t25);
+        let (flow, pi, eta) = self.hpc.lookup(speed, beta_hpc);
+        let w_hpc = self.inlet_hpc.flow(flow, t_t25, p_t25);
+        let t_t3 = gas.compressor_exit_temp(t_t25, pi, eta);
+        let p_t3 = pi*p_t25;
+        cycle.map_point("HPC", speed, beta_hpc, flow, pi, eta);
+
+        let w_core = match w_core {
+            Some (w_core) => {
+                residuals.push((w_hpc - w_core*m_hpc) / self.hpc.flow);
+                w_core
+            },
+            None => w_hpc / m_hpc,
+        };
+        let w_bypass = w_fan - w_core;
+
+        // Combustor
+        let t_t4 = match condition.throttle {
+            Throttle::Temperature (t_t4) => t_t4,
+            Throttle::FuelFlow (_) => x[next] * self.t_t4,
+        };
+        let p_t4 = p_t3;
+        let m3 = 1.0 - variables.customer_bleed_fraction;
+        let f_burner = gas.burner_fuel(t_t3, t_t4, 0.0, 1.0, variables.fuel_delta_h);
+        let w_burner = w_core*m3*(1.0 - self.b_ngv - self.b_rotor);
+        let w_fuel = w_burner*f_burner;
+        if let Throttle::FuelFlow (target) = condition.throttle {
+            residuals.push((w_fuel - target) / self.w_fuel);
+        }
+
+        // High pressure turbine stator, with NGV cooling air mixed in
+        let burner = gas.stream(w_burner + w_fuel, t_t4, p_t4, f_burner);
+        let rotor = if self.b_ngv > 0.0 {
+            gas.merge(burner, gas.stream(w_core*m3*self.b_ngv, t_t3, p_t3, 0.0))
+        } else {
+            burner
+        };
+
+        // High pressure turbine rotor, with rotor cooling air mixed in at the exit
+        let speed = n_h / self.inlet_hpt.theta(rotor.t_t);
+        let (flow, pi, eta) = self.hpt.lookup(speed, beta_hpt);
+        residuals.push((self.inlet_hpt.flow(flow, rotor.t_t, rotor.p_t) - rotor.mdot) / self.w_hpt);
+        let t_t44 = gas.turbine_exit_temp(rotor.t_t, 1.0/pi, eta, rotor.far);
+        let power_hpt = rotor.mdot * (gas.enthalpy(rotor.t_t, rotor.far) - gas.enthalpy(t_t44, rotor.far));
+        let power_hpc = w_core*m_hpc*gas.compressor_work(t_t25, t_t3) + 1000.0*variables.hp_power_offtake;
+        residuals.push((power_hpt - power_hpc) / self.power_hpt);
+        cycle.map_point("HPT", speed, beta_hpt, flow, pi, eta);
+
+        let hpt = gas.stream(rotor.mdot, t_t44, rotor.p_t/pi, rotor.far);
+        let hpt = if self.b_rotor > 0.0 {
+            gas.merge(hpt, gas.stream(w_core*m3*self.b_rotor, t_t3, p_t3, 0.0))
+        } else {
+            hpt
+        };
+
+        // Low pressure turbine
+        let speed = n_l / self.inlet_lpt.theta(hpt.t_t);
+        let (flow, pi, eta) = self.lpt.lookup(speed, beta_lpt);
+        residuals.push((self.inlet_lpt.flow(flow, hpt.t_t, hpt.p_t) - hpt.mdot) / self.w_lpt);
+        let t_t5 = gas.turbine_exit_temp(hpt.t_t, 1.0/pi, eta, hpt.far);
+        let power_lpt = hpt.mdot * (gas.enthalpy(hpt.t_t, hpt.far) - gas.enthalpy(t_t5, hpt.far));
+        let power_fan = w_fan*gas.compressor_work(t_t2, t_t23);
+        let power_lp = w_core*gas.compressor_work(t_t23, t_t25) + 1000.0*variables.lp_power_offtake + if self.engine.geared {
+            power_fan / variables.gearbox_efficiency
+        } else {
+            power_fan
+        };
+        residuals.push((power_lpt - power_lp) / self.power_lpt);
+        cycle.map_point("LPT", speed, beta_lpt, flow, pi, eta);
+
+        let core = gas.stream(hpt.mdot, t_t5, hpt.p_t/pi, hpt.far);
+        let bypass = gas.stream(w_bypass, t_t23, p_t23, 0.0);
+
+        // Nozzles
+        residuals.push((Self::nozzle_flow(core, self.a_9, p_amb) - core.mdot) / self.w_lpt);
+        residuals.push((Self::nozzle_flow(bypass, self.a_19, p_amb) - bypass.mdot) / self.w_bypass);
+
+        cycle.station("2", t_t2, p_t2);
+        cycle.station("23", t_t23, p_t23);
+        if self.engine.booster {
+            cycle.station("25", t_t25, p_t25);
+        }
+        cycle.station("3", t_t3, p_t3);
+        cycle.station("4", t_t4, p_t4);
+        if self.b_ngv > 0.0 {
+            cycle.station("41", rotor.t_t, rotor.p_t);
+        }
+        cycle.station("45", hpt.t_t, hpt.p_t);
+        cycle.station("5", core.t_t, core.p_t);
+        cycle.station("9", core.t_t, core.p_t);
+        cycle.station("19", bypass.t_t, bypass.p_t);
+
+        let v9 = gas.exit_velocity(core, p_amb);
+        let v19 = gas.exit_velocity(bypass, p_amb);
+        cycle.jet("9", core.mdot, v9, gas.exit_density(core, p_amb));
+        cycle.jet("19", bypass.mdot, v19, gas.exit_density(bypass, p_amb));
+
+        cycle.thrust = core.mdot*v9 + bypass.mdot*v19 - w_fan*v0;
+        cycle.sfc = w_fuel/cycle.thrust * 3600.0;
+        cycle.mass_flow = w_fan;
+        cycle.fuel_flow = w_fuel;
+        cycle.fuel_air_ratio = f_burner;
+
+        (residuals, cycle)
+    }
+
+    /// Solves for the operating point of the engine at a flight condition and throttle setting.
+    ///
+    /// Returns `None` if the matching iteration does not converge.
+    pub fn solve(&self, condition: Condition) -> Option<Cycle> {
+        let x = newton::solve(|x| self.evaluate(x, condition).0, self.initial_guess(condition), TOLERANCE, ITERATIONS)?;
+
+        Some (self.evaluate(&x, condition).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn design_point_reproduces_cycle() {
+        let engine = Turbofan::new();
+        let variables = VANILLA_PLUS;
+        let design = engine.cycle(variables);
+
+        let offdesign = OffDesign::new(engine, variables, &Maps::default()).unwrap();
+        let cycle = offdesign.solve(Condition {
+            altitude: 0.0,
+            mach: variables.flight_mach_number,
+            throttle: Throttle::Temperature (variables.hpt_inlet_temp),
+        }).expect("design point should converge");
+
+        for (name, expected, actual) in [
+            ("thrust", design.thrust, cycle.thrust),
+            ("sfc", design.sfc, cycle.sfc),
+            ("mass flow", design.mass_flow, cycle.mass_flow),
+            ("fuel flow", design.fuel_flow, cycle.fuel_flow),
+        ] {
+            assert!((actual - expected).abs() <= 1.0E-6 * expected.abs(), "{}: {} != {}", name, actual, expected);
+        }
+    }
+}
\ No newline at end of file
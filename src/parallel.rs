@@ -0,0 +1,113 @@
+//! Evaluates batches in parallel on scoped threads.
+//!
+//! A batch is split into contiguous chunks, one per thread, and the results are
+//! joined in input order, so that every analysis gives the same results on any
+//! number of threads.  `map` evaluates small batches on the calling thread, since
+//! a design-point cycle takes only microseconds and would not repay the cost of
+//! spawning threads; `map_expensive` splits even small batches item by item, for
+//! optimizer gradients and for off-design solves and missions that take
+//! milliseconds or more each.  The
+//! number of threads is set once with `--threads` and defaults to the available
+//! parallelism of the machine.
+
+use std::{
+    sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        OnceLock,
+    },
+    thread,
+};
+
+/// Smallest number of inputs given to each thread by `map`.
+const MIN_CHUNK: usize = 64;
+
+/// Number of threads, or zero to use the available parallelism.
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Available parallelism of the machine, queried once.
+static AVAILABLE: OnceLock<usize> = OnceLock::new();
+
+/// Sets the number of threads used to evaluate batches, or zero to use the available parallelism.
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads, Ordering::Relaxed);
+}
+
+/// Gives the number of threads used to evaluate batches.
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => *AVAILABLE.get_or_init(|| thread::available_parallelism().map_or(1, |n| n.get())),
+        n => n,
+    }
+}
+
+/// Applies a function to each input, in parallel, returning the results in input order.
+///
+/// Each thread is given at least `MIN_CHUNK` inputs.
+pub fn map<T, U, F>(inputs: &[T], function: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync,
+{
+    map_chunked(inputs, MIN_CHUNK, function)
+}
+
+/// Applies an expensive function to each input, in parallel, returning the results in input order.
+///
+/// Each thread may be given as little as one input.
+pub fn map_expensive<T, U, F>(inputs: &[T], function: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync,
+{
+    map_chunked(inputs, 1, function)
+}
+
+/// Applies a function to each input, giving each thread at least `min_chunk` inputs.
+fn map_chunked<T, U, F>(inputs: &[T], min_chunk: usize, function: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync,
+{
+    // Batches too small to split are evaluated without looking up the thread count
+    if inputs.len() < 2 * min_chunk {
+        return inputs.iter().map(function).collect();
+    }
+
+    let threads = threads().min(inputs.len() / min_chunk);
+    if threads == 1 {
+        return inputs.iter().map(function).collect();
+    }
+
+    let chunk = inputs.len().div_ceil(threads);
+    let function = &function;
+
+    thread::scope(|scope| {
+        let handles = inputs.chunks(chunk)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(function).collect::<Vec<U>>()))
+            .collect::<Vec<_>>();
+
+        handles.into_iter().flat_map(|handle| match handle.join() {
+            Ok (results) => results,
+            Err (panic) => std::panic::resume_unwind(panic),
+        }).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_input_order() {
+        set_threads(4);
+        let inputs = (0..10).collect::<Vec<usize>>();
+
+        assert_eq!(map_expensive(&inputs, |&i| 2*i), (0..10).map(|i| 2*i).collect::<Vec<usize>>());
+    }
+}
@@ -2,6 +2,11 @@
 
 use plotters::prelude::*;
 
+use crate::{
+    maps::ScaledMap,
+    parallel,
+};
+
 /// Plots a function provided.
 pub fn plot<'a, F>(
     function: F,
@@ -11,7 +16,7 @@ pub fn plot<'a, F>(
     independent: &'a str,
     dependent: &'a str,
     filename: &'a str,
-) where F: Fn(f64) -> f64 {
+) where F: Fn(f64) -> f64 + Sync {
     let title = &format!("{} vs. {}", dependent, independent);
 
     let drawing_area = BitMapBackend::new(filename, (1280, 1024))
@@ -22,20 +27,25 @@ pub fn plot<'a, F>(
     let xl = left - (right - left)*0.2;
     let xu = right + (right - left)*0.2;
 
-    let mut series: Vec<(f64, f64)> = Vec::new();
+    let mut inputs: Vec<f64> = Vec::new();
     let mut i = left;
-    let mut min = function(i);
-    let mut max = function(i);
     let di = (right - left)/(n as f64);
     while i < right {
-        let f = function(i);
-        series.push((i, f));
+        inputs.push(i);
+        i += di;
+    }
+
+    let values = parallel::map(&inputs, |&i| function(i));
+    let series: Vec<(f64, f64)> = inputs.into_iter().zip(values).collect();
+
+    let mut min = function(left);
+    let mut max = min;
+    for &(_, f) in &series {
         if f > max {
             max = f;
         } else if f < min {
             min = f;
         }
-        i += di;
     }
 
     let yl = min - (max - min)*0.2;
@@ -59,4 +69,231 @@ pub fn plot<'a, F>(
         .axis_desc_style(("serif", 30))
         .draw()
         .unwrap();
+}
+
+/// Computes padded axis limits enclosing a set of values.
+fn limits(values: impl Iterator<Item = f64> + Clone) -> (f64, f64) {
+    let min = values.clone().fold(f64::INFINITY, f64::min);
+    let max = values.fold(f64::NEG_INFINITY, f64::max);
+
+    (min - (max - min)*0.2, max + (max - min)*0.2)
+}
+
+/// Plots a series of points joined by a line.
+pub fn plot_series<'a>(
+    series: Vec<(f64, f64)>,
+    independent: &'a str,
+    dependent: &'a str,
+    filename: &'a str,
+) {
+    let title = &format!("{} vs. {}", dependent, independent);
+
+    let drawing_area = BitMapBackend::new(filename, (1280, 1024))
+        .into_drawing_area();
+
+    drawing_area.fill(&WHITE).unwrap();
+
+    let (xl, xu) = limits(series.iter().map(|p| p.0));
+    let (yl, yu) = limits(series.iter().map(|p| p.1));
+
+    let mut chart = ChartBuilder::on(&drawing_area)
+        .caption(title, ("serif", 48))
+        .set_label_area_size(LabelAreaPosition::Left, 80)
+        .set_label_area_size(LabelAreaPosition::Bottom, 80)
+        .build_cartesian_2d(xl..xu, yl..yu)
+        .unwrap();
+
+    chart.draw_series(
+        LineSeries::new(series, &BLUE)
+    ).unwrap().label(title);
+
+    chart
+        .configure_mesh()
+        .y_desc(dependent)
+        .x_desc(independent)
+        .axis_desc_style(("serif", 30))
+        .draw()
+        .unwrap();
+}
+
+/// Plots a histogram of a set of values in equal-width bins.
+pub fn plot_histogram<'a>(
+    values: &[f64],
+    bins: usize,
+    independent: &'a str,
+    filename: &'a str,
+) {
+    let title = &format!("{} Distribution", independent);
+
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let width = if max > min { (max - min)/(bins as f64) } else { 1.0 };
+
+    let mut counts = vec![0usize; bins];
+    for value in values {
+        let bin = (((value - min)/width) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    let highest = counts.iter().cloned().max().unwrap_or(0) as f64;
+
+    let drawing_area = BitMapBackend::new(filename, (1280, 1024))
+        .into_drawing_area();
+
+    drawing_area.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&drawing_area)
+        .caption(title, ("serif", 48))
+        .set_label_area_size(LabelAreaPosition::Left, 80)
+        .set_label_area_size(LabelAreaPosition::Bottom, 80)
+        .build_cartesian_2d(min - width..min + (bins as f64 + 1.0)*width, 0.0..highest*1.1)
+        .unwrap();
+
+    chart.draw_series(
+        counts.iter().enumerate().map(|(i, &count)| {
+            let left = min + i as f64*width;
+            Rectangle::new([(left, 0.0), (left + width, count as f64)], BLUE.mix(0.6).filled())
+        })
+    ).unwrap();
+
+    chart
+        .configure_mesh()
+        .y_desc("Samples")
+        .x_desc(independent)
+        .axis_desc_style(("serif", 30))
+        .draw()
+        .unwrap();
+}
+
+/// Plots a bar chart of one or more named series of values over a set of categories.
+pub fn plot_bars<'a>(
+    categories: &[String],
+    series: &[(&'a str, Vec<f64>)],
+    dependent: &'a str,
+    filename: &'a str,
+) {
+    let title = &format!("{} by Variable", dependent);
+    let colors = [BLUE, RED, GREEN, MAGENTA];
+
+    let values = series.iter().flat_map(|s| s.1.iter().cloned());
+    let yl = values.clone().fold(0.0, f64::min) - 0.05;
+    let yu = values.fold(1.0, f64::max) + 0.05;
+
+    let drawing_area = BitMapBackend::new(filename, (1280, 1024))
+        .into_drawing_area();
+
+    drawing_area.fill(&WHITE).unwrap();
+
+    // Keep at least two segments, since a single segment has no extent
+    let segments = categories.len().max(2);
+    let mut chart = ChartBuilder::on(&drawing_area)
+        .caption(title, ("serif", 48))
+        .set_label_area_size(LabelAreaPosition::Left, 80)
+        .set_label_area_size(LabelAreaPosition::Bottom, 80)
+        .build_cartesian_2d((0..segments as i32 - 1).into_segmented(), yl..yu)
+        .unwrap();
+
+    // Share each category between the series, leaving a gap on either side
+    let width = (1280 - 80) / (segments as u32);
+    let bar = (width * 8 / 10) / (series.len().max(1) as u32);
+
+    for (k, (label, values)) in series.iter().enumerate() {
+        let color = colors[k % colors.len()];
+        let left = width / 10 + k as u32 * bar;
+        let right = width - left - bar;
+
+        chart.draw_series(
+            values.iter().enumerate().map(|(i, &value)| {
+                let mut rectangle = Rectangle::new(
+                    [(SegmentValue::Exact(i as i32), 0.0), (SegmentValue::Exact(i as i32 + 1), value)],
+                    color.mix(0.6).filled(),
+                );
+                rectangle.set_margin(0, 0, left, right);
+                rectangle
+            })
+        ).unwrap()
+            .label(*label)
+            .legend(move |(x, y)| Rectangle::new([(x, y - 8), (x + 16, y + 8)], color.mix(0.6).filled()));
+    }
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(categories.len())
+        .x_label_formatter(&|value| match value {
+            SegmentValue::CenterOf (i) => categories.get(*i as usize).cloned().unwrap_or_default(),
+            _ => String::new(),
+        })
+        .y_desc(dependent)
+        .axis_desc_style(("serif", 30))
+        .draw()
+        .unwrap();
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .position(SeriesLabelPosition::UpperRight)
+        .draw()
+        .unwrap();
+}
+
+/// Plots an operating line over the speed lines of a scaled compressor map.
+pub fn plot_map<'a>(
+    map: &ScaledMap,
+    operating: Vec<(f64, f64)>,
+    component: &'a str,
+    filename: &'a str,
+) {
+    let title = &format!("{} Operating Line", component);
+    let n: usize = 50;
+
+    // Speed lines, from the surge side (beta = 0) to the choke side (beta = 1)
+    let lines = map.map.speeds.iter().map(|speed| {
+        (0..=n).map(|j| {
+            let (flow, ratio, _) = map.lookup(speed/map.map.design_speed, j as f64/n as f64);
+            (flow, ratio)
+        }).collect::<Vec<(f64, f64)>>()
+    }).collect::<Vec<Vec<(f64, f64)>>>();
+    let surge = lines.iter().map(|line| line[0]).collect::<Vec<(f64, f64)>>();
+
+    let drawing_area = BitMapBackend::new(filename, (1280, 1024))
+        .into_drawing_area();
+
+    drawing_area.fill(&WHITE).unwrap();
+
+    let (xl, xu) = limits(lines.iter().flatten().chain(operating.iter()).map(|p| p.0));
+    let (yl, yu) = limits(lines.iter().flatten().chain(operating.iter()).map(|p| p.1));
+
+    let mut chart = ChartBuilder::on(&drawing_area)
+        .caption(title, ("serif", 48))
+        .set_label_area_size(LabelAreaPosition::Left, 80)
+        .set_label_area_size(LabelAreaPosition::Bottom, 80)
+        .build_cartesian_2d(xl..xu, yl..yu)
+        .unwrap();
+
+    for line in lines {
+        chart.draw_series(
+            LineSeries::new(line, &BLACK.mix(0.4))
+        ).unwrap();
+    }
+
+    chart.draw_series(
+        LineSeries::new(surge, &RED)
+    ).unwrap();
+
+    chart.draw_series(
+        LineSeries::new(operating.clone(), &BLUE)
+    ).unwrap();
+
+    chart.draw_series(
+        operating.into_iter().map(|p| Circle::new(p, 4, BLUE.filled()))
+    ).unwrap();
+
+    chart
+        .configure_mesh()
+        .y_desc("Total Pressure Ratio")
+        .x_desc("Corrected Mass Flow (kg/s)")
+        .axis_desc_style(("serif", 30))
+        .draw()
+        .unwrap();
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+//! Computes the performance of a ramjet.
+
+use crate::{
+    constants::*,
+    flow::{
+        inlet_recovery,
+        mass_flow_parameter,
+        rayleigh_mach,
+        rayleigh_pressure_ratio,
+        rayleigh_temperature_ratio,
+        total_pressure_ratio,
+        total_temperature_ratio,
+    },
+    gas::Gas,
+    Cycle,
+    Engine,
+    Fuel,
+    GasModel,
+    Variables,
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Constructs a ramjet with a convergent-divergent nozzle.
+pub struct Ramjet {
+    /// Gas property model
+    pub gas: GasModel,
+
+    /// Fuel burned
+    pub fuel: Fuel,
+}
+
+impl Ramjet {
+    /// Constructs a new ramjet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Engine for Ramjet {
+    /// Computes the thermodynamic cycle of this engine.
+    ///
+    /// The inlet diameter sets the capture area.  The burner exit temperature is
+    /// limited to `BURNER_EXIT_TEMP_MAX` and to the onset of thermal choking.
+    fn cycle(&self, variables: Variables) -> Cycle {
+        let mut cycle = Cycle::default();
+        let gas = Gas::new(self.gas, self.fuel, variables);
+
+        // Free stream
+        let m0 = variables.flight_mach_number;
+        let v0 = m0 * (GAMMA * R * T0).sqrt();
+        let t_t0 = T0 * total_temperature_ratio(m0, GAMMA);
+        let p_t0 = P0 * total_pressure_ratio(m0, GAMMA);
+
+        // Captured mass flow rate
+        let mdot = p_t0 * variables.inlet_area() * mass_flow_parameter(m0, GAMMA) / t_t0.sqrt();
+
+        // Inlet
+        let t_t2 = t_t0;
+        let p_t2 = p_t0 * inlet_recovery(m0) * variables.inlet_efficiency;
+
+        // Burner
+        let m3 = variables.burner_mach_number;
+        let t_t3 = t_t2;
+        let p_t3 = p_t2;
+        let t_t_choked = t_t3 / rayleigh_temperature_ratio(m3, GAMMA);
+        let t_t4 = variables.burner_exit_temp.min(BURNER_EXIT_TEMP_MAX).min(t_t_choked).max(t_t3);
+        let m4 = rayleigh_mach(t_t4 / t_t_choked, GAMMA);
+        let p_t4 = p_t3 * rayleigh_pressure_ratio(m4, GAMMA) / rayleigh_pressure_ratio(m3, GAMMA);
+        let f = gas.burner_fuel(t_t3, t_t4, 0.0, variables.combustor_efficiency, variables.fuel_delta_h);
+
+        // Convergent-divergent nozzle
+        let exhaust = gas.stream(1.0 + f, t_t4, p_t4, f);
+        let v9 = gas.exit_velocity(exhaust, P0);
+
+        cycle.station("0", t_t0, p_t0);
+        cycle.station("2", t_t2, p_t2);
+        cycle.station("4", t_t4, p_t4);
+        cycle.station("9", t_t4, p_t4);
+        cycle.jet("9", mdot * exhaust.mdot, v9, gas.exit_density(exhaust, P0));
+
+        cycle.thrust = mdot * ((1.0 + f)*v9 - v0);
+        cycle.sfc = f * mdot/cycle.thrust * 3600.0;
+        cycle.mass_flow = mdot;
+        cycle.fuel_flow = f * mdot;
+        cycle.fuel_air_ratio = f;
+
+        cycle
+    }
+
+    /// Computes the overall pressure ratio of this engine, which has no compressor.
+    fn overall_pressure_ratio(&self, _variables: Variables) -> f64 {
+        1.0
+    }
+
+    /// Names this engine.
+    fn name(&self) -> String {
+        "Ramjet".to_string()
+    }
+
+    /// Gives the fuel burned by this engine.
+    fn fuel(&self) -> Fuel {
+        self.fuel
+    }
+
+    /// Indicates that this engine requires a flight Mach number.
+    fn needs_flight_mach(&self) -> bool {
+        true
+    }
+}
\ No newline at end of file
@@ -0,0 +1,169 @@
+//! Provides a seeded pseudorandom number generator and sampling distributions.
+//!
+//! Numbers are drawn with the SplitMix64 generator, so that a given seed always
+//! reproduces the same samples on every platform.  Distributions are sampled by
+//! inverting their cumulative distribution functions, which lets any source of
+//! uniform numbers (random or stratified) drive them.
+
+use std::{
+    process,
+    str::FromStr,
+};
+
+#[derive(Clone, Copy, Debug)]
+/// Generates reproducible pseudorandom numbers from a seed.
+pub struct Random {
+    /// Generator state
+    state: u64,
+}
+
+impl Random {
+    /// Constructs a new generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: seed,
+        }
+    }
+
+    /// Draws the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Draws a number uniformly distributed on the open interval (0, 1).
+    pub fn uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+}
+
+/// Computes the inverse of the standard normal cumulative distribution function.
+///
+/// Uses the rational approximation of Acklam, with a relative error below 1.2E-9.
+fn inverse_normal(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376E+1, 2.209460984245205E+2, -2.759285104469687E+2, 1.38357751867269E+2, -3.066479806614716E+1, 2.506628277459239];
+    const B: [f64; 5] = [-5.447609879822406E+1, 1.615858368580409E+2, -1.556989798598866E+2, 6.680131188771972E+1, -1.328068155288572E+1];
+    const C: [f64; 6] = [-7.784894002430293E-3, -3.223964580411365E-1, -2.400758277161838, -2.549732539343734, 4.374664141464968, 2.938163982698783];
+    const D: [f64; 4] = [7.784695709041462E-3, 3.224671290700398E-1, 2.445134137142996, 3.754408661907416];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| (((((C[0]*q + C[1])*q + C[2])*q + C[3])*q + C[4])*q + C[5]) / ((((D[0]*q + D[1])*q + D[2])*q + D[3])*q + 1.0);
+
+    if p < P_LOW {
+        tail((-2.0*p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0*(1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q*q;
+        (((((A[0]*r + A[1])*r + A[2])*r + A[3])*r + A[4])*r + A[5])*q / (((((B[0]*r + B[1])*r + B[2])*r + B[3])*r + B[4])*r + 1.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Describes the distribution of an uncertain variable.
+pub enum Distribution {
+    /// Normal distribution with a mean and standard deviation
+    Normal {
+        mean: f64,
+        std_dev: f64,
+    },
+
+    /// Uniform distribution between two limits
+    Uniform {
+        min: f64,
+        max: f64,
+    },
+
+    /// Triangular distribution between two limits, peaking at the mode
+    Triangular {
+        min: f64,
+        mode: f64,
+        max: f64,
+    },
+}
+
+impl From<&str> for Distribution {
+    fn from(spec: &str) -> Self {
+        spec.parse().unwrap_or_else(|error| {
+            println!("[FATAL] {}", error);
+            process::exit(0);
+        })
+    }
+}
+
+impl FromStr for Distribution {
+    type Err = String;
+
+    /// Parses a distribution of the form `normal:mean,std_dev`, `uniform:min,max` or `triangular:min,mode,max`.
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let (kind, values) = spec.split_once(':')
+            .ok_or_else(|| format!("Could not parse distribution {}.  Type `jetlab` for help.", spec))?;
+
+        let values = values.split(',').map(|value| str::parse::<f64>(value)
+            .map_err(|_| format!("Could not parse {} as numeric value in distribution {}", value, spec))
+        ).collect::<Result<Vec<f64>, String>>()?;
+
+        match (kind, values.as_slice()) {
+            ("normal", &[mean, std_dev]) if std_dev >= 0.0 => Ok (Distribution::Normal { mean, std_dev }),
+            ("uniform", &[min, max]) if min <= max => Ok (Distribution::Uniform { min, max }),
+            ("triangular", &[min, mode, max]) if min <= mode && mode <= max => Ok (Distribution::Triangular { min, mode, max }),
+            _ => Err (format!("Invalid distribution {}.  Type `jetlab` for help.", spec)),
+        }
+    }
+}
+
+impl Distribution {
+    /// Computes the value at a cumulative probability `p` between 0 and 1.
+    pub fn quantile(&self, p: f64) -> f64 {
+        match *self {
+            Distribution::Normal { mean, std_dev } => mean + std_dev * inverse_normal(p),
+            Distribution::Uniform { min, max } => min + p * (max - min),
+            Distribution::Triangular { min, mode, max } => {
+                let split = if max > min { (mode - min) / (max - min) } else { 0.5 };
+                if p < split {
+                    min + (p * (max - min) * (mode - min)).sqrt()
+                } else {
+                    max - ((1.0 - p) * (max - min) * (max - mode)).sqrt()
+                }
+            },
+        }
+    }
+
+    /// Draws a random value.
+    pub fn sample(&self, random: &mut Random) -> f64 {
+        self.quantile(random.uniform())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_normal_matches_table() {
+        assert!((inverse_normal(0.975) - 1.959964).abs() < 1.0E-6);
+        assert!(inverse_normal(0.5).abs() < 1.0E-12);
+        assert!((inverse_normal(0.01) + 2.326348).abs() < 1.0E-6);
+    }
+
+    #[test]
+    fn quantiles_span_limits() {
+        let triangular: Distribution = "triangular:1,2,4".parse().unwrap();
+        assert!((triangular.quantile(0.0) - 1.0).abs() < 1.0E-12);
+        assert!((triangular.quantile(1.0 / 3.0) - 2.0).abs() < 1.0E-12);
+        assert!((triangular.quantile(1.0) - 4.0).abs() < 1.0E-12);
+
+        let uniform: Distribution = "uniform:2,6".parse().unwrap();
+        assert!((uniform.quantile(0.25) - 3.0).abs() < 1.0E-12);
+    }
+
+    #[test]
+    fn rejects_bad_distributions() {
+        let error = "uniform:2,1".parse::<Distribution>().unwrap_err();
+        assert_eq!(error, "Invalid distribution uniform:2,1.  Type `jetlab` for help.");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,288 @@
+//! Holds the aircraft requirements that constrain an engine design.
+//!
+//! Requirements default to the constants in `constants.rs` and may be read at
+//! runtime from a text file of `key value` lines.  Lines starting with `#` are
+//! comments, and keys that are not given keep their default values:
+//!
+//! ```text
+//! # Aircraft requirements
+//! thrust                  160135.978
+//! engine_count            2
+//! thrust_safety_margin    0.01
+//! shaft_power             10000
+//! overall_pressure_ratio  47
+//! compressor_exit_temp    1172.039
+//! turbine_inlet_temp      1699.817
+//! inlet_diameter_min      1.2192
+//! inlet_diameter_max      1.3716
+//! dry_weight              2500
+//! engine_length           3.0
+//! nox_index               60
+//! ```
+//!
+//! Dry weight, engine length and the design-point NOx emissions index are
+//! unlimited unless given.
+
+use std::{
+    fs,
+    path::Path,
+    process,
+    str::FromStr,
+};
+
+use crate::{
+    constants::*,
+    Cycle,
+    Emissions,
+    Engine,
+    Variables,
+};
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the aircraft requirements on thrust and the limits on an engine design.
+pub struct Requirements {
+    /// Total takeoff thrust required of all engines (in N)
+    pub thrust: f64,
+
+    /// Number of aircraft engines
+    pub engine_count: usize,
+
+    /// Thrust safety margin
+    pub thrust_safety_margin: f64,
+
+    /// Minimum shaft power of each engine (in kW), for shaft power engines
+    pub shaft_power: f64,
+
+    /// Overall pressure ratio held by the optimizer
+    pub overall_pressure_ratio: f64,
+
+    /// Maximum compressor exit temperature (in K)
+    pub compressor_exit_temp: f64,
+
+    /// Maximum turbine inlet temperature (in K)
+    pub turbine_inlet_temp: f64,
+
+    /// Minimum inlet diameter (in m)
+    pub inlet_diameter_min: f64,
+
+    /// Maximum inlet diameter (in m)
+    pub inlet_diameter_max: f64,
+
+    /// Maximum dry weight of each engine (in kg)
+    pub dry_weight: f64,
+
+    /// Maximum length of each engine (in m)
+    pub engine_length: f64,
+
+    /// Maximum NOx emissions index at the design point (in g/kg of fuel)
+    pub nox_index: f64,
+}
+
+impl Default for Requirements {
+    /// Constructs the requirements given by `constants.rs`.
+    fn default() -> Self {
+        Self {
+            thrust: REQUIRED_THRUST,
+            engine_count: ENGINE_COUNT,
+            thrust_safety_margin: THRUST_SAFETY_MARGIN,
+            shaft_power: MIN_SHAFT_POWER,
+            overall_pressure_ratio: OPR,
+            compressor_exit_temp: HPC_DISCHARGE_TEMP_MAX,
+            turbine_inlet_temp: HPT_INLET_TEMP_MAX,
+            inlet_diameter_min: INLET_DIAMETER_MIN,
+            inlet_diameter_max: INLET_DIAMETER_MAX,
+            dry_weight: f64::INFINITY,
+            engine_length: f64::INFINITY,
+            nox_index: f64::INFINITY,
+        }
+    }
+}
+
+impl From<&str> for Requirements {
+    fn from(text: &str) -> Self {
+        text.parse().unwrap_or_else(|error| {
+            println!("[FATAL] {}", error);
+            process::exit(0);
+        })
+    }
+}
+
+impl FromStr for Requirements {
+    type Err = String;
+
+    /// Parses requirements written as `key value` lines.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut requirements = Requirements::default();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let key = words.next().unwrap_or_default();
+            let value = match (words.next(), words.next()) {
+                (Some (value), None) => value,
+                _ => return Err (format!("Requirement {} expects one value", key)),
+            };
+
+            if key == "engine_count" {
+                requirements.engine_count = match str::parse::<usize>(value) {
+                    Ok (count) if count > 0 => count,
+                    _ => return Err (format!("Could not parse {} as a positive whole number of engines", value)),
+                };
+                continue;
+            }
+
+            let value = str::parse::<f64>(value)
+                .map_err(|_| format!("Could not parse {} as numeric value for requirement {}", value, key))?;
+
+            match key {
+                "thrust" => requirements.thrust = value,
+                "thrust_safety_margin" => requirements.thrust_safety_margin = value,
+                "shaft_power" => requirements.shaft_power = value,
+                "overall_pressure_ratio" => requirements.overall_pressure_ratio = value,
+                "compressor_exit_temp" => requirements.compressor_exit_temp = value,
+                "turbine_inlet_temp" => requirements.turbine_inlet_temp = value,
+                "inlet_diameter_min" => requirements.inlet_diameter_min = value,
+                "inlet_diameter_max" => requirements.inlet_diameter_max = value,
+                "dry_weight" => requirements.dry_weight = value,
+                "engine_length" => requirements.engine_length = value,
+                "nox_index" => requirements.nox_index = value,
+                _ => return Err (format!("Unrecognized requirement {}", key)),
+            }
+        }
+
+        Ok (requirements)
+    }
+}
+
+impl Requirements {
+    /// Reads requirements from a file.
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok (text) => text.as_str().into(),
+            Err (_) => {
+                println!("[FATAL] Could not read requirements {}", path.display());
+                process::exit(0);
+            },
+        }
+    }
+
+    /// Computes the minimum allowable thrust of each engine (in N).
+    pub fn min_thrust(&self) -> f64 {
+        self.thrust * (1.0 + self.thrust_safety_margin) / self.engine_count as f64
+    }
+
+    /// Brings a design within the temperature limits.
+    ///
+    /// The turbine inlet temperature is clamped, and the HPC pressure ratio is
+    /// reduced until the compressor exit temperature is met.  The inlet diameter
+    /// is bounded by `Bounds`, so its limits here are only checked.
+    pub fn constrain<E: Engine + ?Sized>(&self, engine: &E, mut variables: Variables) -> Variables {
+        variables.hpt_inlet_temp = variables.hpt_inlet_temp.min(self.turbine_inlet_temp);
+
+        for _ in 0..10 {
+            let cycle = engine.cycle(variables);
+            match cycle.stations.iter().find(|s| s.name == "3") {
+                Some (station) if station.total_temp > self.compressor_exit_temp => {
+                    variables.hpc_pressure_ratio *= (self.compressor_exit_temp / station.total_temp).powf(GAMMA / (GAMMA - 1.0));
+                },
+                _ => break,
+            }
+        }
+
+        variables
+    }
+
+    /// Checks a design against each requirement.
+    ///
+    /// Returns the name of each requirement, with a description of the violation
+    /// if the design does not meet it.  Requirements that do not apply to an
+    /// engine are always met.
+    pub fn check<E: Engine + ?Sized>(&self, engine: &E, variables: Variables) -> Vec<(&'static str, Option<String>)> {
+        self.check_cycle(engine, variables, &engine.cycle(variables))
+    }
+
+    /// Checks a design against each requirement, given its computed cycle.
+    pub fn check_cycle<E: Engine + ?Sized>(&self, engine: &E, variables: Variables, cycle: &Cycle) -> Vec<(&'static str, Option<String>)> {
+        let mut checks = Vec::new();
+
+        let (thrust, _) = engine.analyze_cycle(cycle);
+        let min_thrust = engine.min_thrust(self);
+        checks.push(("thrust", (thrust < min_thrust).then(|| {
+            format!("{} of {:.1} is below the required {:.1}", engine.thrust_label(), thrust, min_thrust)
+        })));
+
+        let opr = engine.overall_pressure_ratio(variables);
+        checks.push(("overall_pressure_ratio", (opr > self.overall_pressure_ratio * (1.0 + 1.0E-9)).then(|| {
+            format!("Overall pressure ratio of {:.2} is above the limit of {:.2}", opr, self.overall_pressure_ratio)
+        })));
+
+        let t_t3 = cycle.stations.iter().find(|s| s.name == "3").map_or(0.0, |s| s.total_temp);
+        checks.push(("compressor_exit_temp", (t_t3 > self.compressor_exit_temp).then(|| {
+            format!("Compressor exit temperature of {:.1} K is above the limit of {:.1} K", t_t3, self.compressor_exit_temp)
+        })));
+
+        // Engines without a turbine have no turbine inlet temperature to limit
+        let turbine = cycle.stations.iter().any(|s| s.name == "45");
+        let t_t4 = cycle.stations.iter().find(|s| s.name == "4" && turbine).map_or(0.0, |s| s.total_temp);
+        checks.push(("turbine_inlet_temp", (t_t4 > self.turbine_inlet_temp).then(|| {
+            format!("Turbine inlet temperature of {:.1} K is above the limit of {:.1} K", t_t4, self.turbine_inlet_temp)
+        })));
+
+        let fuel = engine.fuel();
+        checks.push(("fuel_air_ratio", (cycle.fuel_air_ratio > fuel.stoichiometric_ratio()).then(|| {
+            format!("Burner fuel-air ratio of {:.4} is above the stoichiometric {:.4} of {}", cycle.fuel_air_ratio, fuel.stoichiometric_ratio(), fuel.name)
+        })));
+
+        let diameter = variables.inlet_diameter;
+        checks.push(("inlet_diameter", (diameter < self.inlet_diameter_min || diameter > self.inlet_diameter_max).then(|| {
+            format!("Inlet diameter of {:.4} m is outside {:.4} to {:.4} m", diameter, self.inlet_diameter_min, self.inlet_diameter_max)
+        })));
+
+        let nox_index = Emissions::new(cycle, fuel).map_or(0.0, |e| e.nox_index);
+        checks.push(("nox_index", (nox_index > self.nox_index).then(|| {
+            format!("NOx emissions index of {:.3} g/kg is above the limit of {:.3} g/kg", nox_index, self.nox_index)
+        })));
+
+        let size = engine.size_cycle(variables, cycle);
+        let weight = size.as_ref().map_or(0.0, |size| size.weight);
+        checks.push(("dry_weight", (weight > self.dry_weight).then(|| {
+            format!("Dry weight of {:.1} kg is above the limit of {:.1} kg", weight, self.dry_weight)
+        })));
+
+        let length = size.as_ref().map_or(0.0, |size| size.length);
+        checks.push(("engine_length", (length > self.engine_length).then(|| {
+            format!("Engine length of {:.3} m is above the limit of {:.3} m", length, self.engine_length)
+        })));
+
+        checks
+    }
+
+    /// Lists the requirements that a design does not meet.
+    pub fn violations<E: Engine + ?Sized>(&self, engine: &E, variables: Variables) -> Vec<String> {
+        self.check(engine, variables).into_iter().filter_map(|(_, violation)| violation).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_requirements() {
+        let requirements: Requirements = "# Aircraft requirements\nthrust 200000\nengine_count 4\n".parse().unwrap();
+
+        assert_eq!(requirements.thrust, 200000.0);
+        assert_eq!(requirements.engine_count, 4);
+        assert_eq!(requirements.overall_pressure_ratio, OPR);
+    }
+
+    #[test]
+    fn rejects_bad_requirements() {
+        let error = "thrust x".parse::<Requirements>().unwrap_err();
+        assert_eq!(error, "Could not parse x as numeric value for requirement thrust");
+    }
+}
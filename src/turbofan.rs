@@ -1,272 +1,661 @@
 //! Computes the performance of a turbofan.
 
+use std::process;
+
 use crate::{
     constants::*,
-    plot,
+    flow::{
+        compressor_isentropic_efficiency,
+        compressor_polytropic_efficiency,
+        inlet_mass_flow,
+        mix,
+        reheat,
+        turbine_isentropic_efficiency,
+        turbine_polytropic_efficiency,
+    },
+    gas::Gas,
+    offdesign::OffDesign,
+    weight::{
+        station,
+        Module,
+        HPC_STAGE_PRESSURE_RATIO,
+        HPT_BLADE_SPEED,
+        IPC_STAGE_PRESSURE_RATIO,
+        IPT_BLADE_SPEED,
+        LPC_STAGE_PRESSURE_RATIO,
+        LPT_BLADE_SPEED,
+        MAX_LPT_BLADE_SPEED,
+    },
+    Condition,
+    Cycle,
+    Engine,
+    Fuel,
+    GasModel,
+    Maps,
+    Nacelle,
+    ScaledMap,
+    Size,
     Variables,
-    VarSelector,
 };
 
+#[derive(Clone, Copy, Debug, Default)]
+/// Selects how the core and bypass streams leave the engine.
+pub enum Exhaust {
+    /// Separate core (station 9) and bypass (station 19) nozzles
+    #[default]
+    Separate,
+
+    /// Core and bypass streams combined in a mixer ahead of a common nozzle
+    Mixed,
+}
+
+impl From<String> for Exhaust {
+    fn from(string: String) -> Self {
+        match string.as_str() {
+            "separate" => Exhaust::Separate,
+            "mixed" => Exhaust::Mixed,
+            _ => {
+                println!("[FATAL] Invalid exhaust {}.  Type `jetlab` for help.", &string);
+                process::exit(0);
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Selects the number of spools.
+pub enum Spools {
+    /// Fan (and booster) on the low spool, HPC on the high spool
+    #[default]
+    Two,
+
+    /// Fan (and booster) on the low spool, IPC on the intermediate spool, HPC on the high spool
+    Three,
+}
+
+impl From<String> for Spools {
+    fn from(string: String) -> Self {
+        match string.as_str() {
+            "2" => Spools::Two,
+            "3" => Spools::Three,
+            _ => {
+                println!("[FATAL] Invalid number of spools {}.  Type `jetlab` for help.", &string);
+                process::exit(0);
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Selects the compressor station from which customer bleed air is taken.
+pub enum BleedStation {
+    /// LPC exit (station 25)
+    Lpc,
+
+    /// IPC exit (station 26)
+    Ipc,
+
+    /// HPC exit (station 3)
+    #[default]
+    Hpc,
+}
+
+impl From<String> for BleedStation {
+    fn from(string: String) -> Self {
+        match string.as_str() {
+            "25" => BleedStation::Lpc,
+            "26" => BleedStation::Ipc,
+            "3" => BleedStation::Hpc,
+            _ => {
+                println!("[FATAL] Invalid bleed station {}.  Type `jetlab` for help.", &string);
+                process::exit(0);
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Selects how the HPT cooling air is sized.
+pub enum Cooling {
+    /// Uncooled HPT
+    #[default]
+    None,
+
+    /// NGV and rotor cooling air given by `ngv_cooling_fraction` and `rotor_cooling_fraction`
+    Fixed,
+
+    /// NGV and rotor cooling air sized to hold `blade_metal_temp`
+    Metal,
+}
+
+impl From<String> for Cooling {
+    fn from(string: String) -> Self {
+        match string.as_str() {
+            "none" => Cooling::None,
+            "fixed" => Cooling::Fixed,
+            "metal" => Cooling::Metal,
+            _ => {
+                println!("[FATAL] Invalid cooling model {}.  Type `jetlab` for help.", &string);
+                process::exit(0);
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Selects which turbomachinery efficiencies are given as polytropic rather than isentropic efficiencies.
+pub struct Polytropic {
+    /// Fan
+    pub fan: bool,
+
+    /// Low pressure compressor (booster)
+    pub lpc: bool,
+
+    /// Intermediate pressure compressor
+    pub ipc: bool,
+
+    /// High pressure compressor
+    pub hpc: bool,
+
+    /// High pressure turbine
+    pub hpt: bool,
+
+    /// Intermediate pressure turbine
+    pub ipt: bool,
+
+    /// Low pressure turbine
+    pub lpt: bool,
+}
+
+impl Polytropic {
+    /// Marks a component (or `all` components) as specified by polytropic efficiency.
+    pub fn set(&mut self, component: &str) {
+        match component {
+            "fan" => self.fan = true,
+            "lpc" => self.lpc = true,
+            "ipc" => self.ipc = true,
+            "hpc" => self.hpc = true,
+            "hpt" => self.hpt = true,
+            "ipt" => self.ipt = true,
+            "lpt" => self.lpt = true,
+            "all" => *self = Self {
+                fan: true,
+                lpc: true,
+                ipc: true,
+                hpc: true,
+                hpt: true,
+                ipt: true,
+                lpt: true,
+            },
+            _ => {
+                println!("[FATAL] Invalid component {}.  Type `jetlab` for help.", component);
+                process::exit(0);
+            },
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 /// Constructs a turbofan.
-pub struct Turbofan { }
+pub struct Turbofan {
+    /// Exhaust arrangement
+    pub exhaust: Exhaust,
+
+    /// Whether the afterburner is lit
+    pub afterburner: bool,
+
+    /// Spool layout
+    pub spools: Spools,
+
+    /// Whether a booster (LPC) rides on the low spool behind the fan
+    pub booster: bool,
+
+    /// Whether the fan is driven through a reduction gearbox
+    pub geared: bool,
+
+    /// Gas property model
+    pub gas: GasModel,
+
+    /// Fuel burned
+    pub fuel: Fuel,
+
+    /// Components whose efficiencies are polytropic
+    pub polytropic: Polytropic,
+
+    /// HPT cooling model
+    pub cooling: Cooling,
+
+    /// Station from which customer bleed air is taken
+    pub bleed_station: BleedStation,
+}
+
+impl Default for Turbofan {
+    fn default() -> Self {
+        Self {
+            exhaust: Exhaust::Separate,
+            afterburner: false,
+            spools: Spools::Two,
+            booster: true,
+            geared: false,
+            gas: GasModel::Perfect,
+            fuel: Fuel::default(),
+            polytropic: Polytropic::default(),
+            cooling: Cooling::None,
+            bleed_station: BleedStation::Hpc,
+        }
+    }
+}
 
 impl Turbofan {
-    /// Constructs a new turbofan.
+    /// Constructs a new direct-drive two-spool turbofan with a booster, separate exhaust streams and no afterburner.
     pub fn new() -> Self {
-        Self { }
+        Self::default()
     }
+}
+
+impl Turbofan {
+    /// Computes the NGV and rotor cooling air, as fractions of HPC exit flow.
+    ///
+    /// With `Cooling::Metal`, each blade row takes `cooling_flow_factor * e/(1 - e)` of
+    /// the HPC exit flow, where `e = (Tg - Tm)/(Tg - Tc)` is the cooling effectiveness
+    /// needed to hold the blade metal temperature `Tm` in gas at `Tg` with coolant at `Tc`.
+    /// The NGV sees the burner exit temperature and the rotor sees the mixed-out
+    /// stator exit temperature.  The effectiveness is limited to 0.9.
+    pub(crate) fn cooling_fractions(&self, gas: &Gas, variables: Variables, t_t3: f64, t_t4: f64, f: f64) -> (f64, f64) {
+        match self.cooling {
+            Cooling::None => (0.0, 0.0),
+            Cooling::Fixed => (variables.ngv_cooling_fraction, variables.rotor_cooling_fraction),
+            Cooling::Metal => {
+                let cooling_flow = |t_gas: f64| {
+                    let e = ((t_gas - variables.blade_metal_temp) / (t_gas - t_t3)).clamp(0.0, 0.9);
+                    variables.cooling_flow_factor * e/(1.0 - e)
+                };
+
+                let ngv = cooling_flow(t_t4);
+
+                // The stator exit temperature depends on the burner flow, and so on the rotor cooling air
+                let mut rotor = 0.0;
+                for _ in 0..20 {
+                    let burner = gas.stream((1.0 - ngv - rotor)*(1.0 + f), t_t4, 1.0, f);
+                    let t_t41 = gas.merge(burner, gas.stream(ngv, t_t3, 1.0, 0.0)).t_t;
+                    rotor = cooling_flow(t_t41);
+                }
+
+                (ngv, rotor)
+            },
+        }
+    }
+
+    /// Converts a compressor efficiency to an isentropic efficiency, recording both in the cycle.
+    fn compressor_efficiency(gas: &Gas, cycle: &mut Cycle, name: &'static str, polytropic: bool, t_in: f64, pi: f64, eta: f64) -> f64 {
+        let gamma = gas.effective_gamma(t_in, pi, 0.0);
+        let eta = if polytropic {
+            compressor_isentropic_efficiency(pi, eta, gamma)
+        } else {
+            eta
+        };
+        cycle.efficiency(name, eta, compressor_polytropic_efficiency(pi, eta, gamma));
+
+        eta
+    }
+
+    /// Expands a turbine delivering a given work (in J/kg of gas), recording both efficiencies in the cycle.
+    ///
+    /// Returns the exit total temperature and total pressure ratio.
+    #[allow(clippy::too_many_arguments)]
+    fn turbine(gas: &Gas, cycle: &mut Cycle, name: &'static str, polytropic: bool, t_in: f64, work: f64, eta: f64, far: f64) -> (f64, f64) {
+        let eta = if polytropic {
+            // The exit temperature is set by the work alone
+            let (t_out, _) = gas.turbine(t_in, work, 1.0, far);
+            turbine_isentropic_efficiency(t_out/t_in, eta)
+        } else {
+            eta
+        };
+        let (t_out, pi) = gas.turbine(t_in, work, eta, far);
+        cycle.efficiency(name, eta, turbine_polytropic_efficiency(t_out/t_in, pi, gas.effective_gamma(t_in, pi, far)));
+
+        (t_out, pi)
+    }
+}
+
+impl Engine for Turbofan {
+    /// Computes the thermodynamic cycle of this engine.
+    ///
+    /// HPT cooling air is bled from the HPC exit.  NGV cooling air mixes in ahead of
+    /// the HPT rotor (station 41) and rotor cooling air, which does no work, mixes in at
+    /// the rotor exit.
+    ///
+    /// Customer bleed air is taken from `bleed_station` and dumped overboard, and shaft
+    /// power offtakes are added to the work drawn from the HP and LP spools.
+    ///
+    /// Efficiencies of the components selected in `polytropic` are read as polytropic
+    /// efficiencies and converted to isentropic efficiencies at the effective ratio of
+    /// specific heats over the component.  With the afterburner lit, this gives the wet
+    /// thrust and wet SFC.
+    fn cycle(&self, variables: Variables) -> Cycle {
+        let mut cycle = Cycle::default();
+        let gas = Gas::new(self.gas, self.fuel, variables);
+        let poly = self.polytropic;
 
-    /// Computes the thrust and SFC of this engine.
-    pub fn analyze(&self, variables: Variables) -> (f64, f64) {
         let t_t1 = T0;
         let p_t1 = P0;
-        let a1 = variables.inlet_area();
-        let m1 = variables.inlet_mach_number;
 
         // Compute mass flow rate
-        let mdot = p_t1*a1*t_t1.powf(-0.5) * (GAMMA / R).sqrt() * m1 * (1.0 + (GAMMA - 1.0)/2.0 * m1.powi(2)).powf((GAMMA + 1.0)/(2.0*(1.0 - GAMMA)));
+        let mdot = inlet_mass_flow(variables);
 
         // Fan
         let t_t2 = t_t1;
         let p_t2 = p_t1;
-        let eta = variables.fan_efficiency;
-        let pi = variables.fan_pressure_ratio;
-        let t_t23 = t_t2 * (1.0 + 1.0/eta * (pi.powf((GAMMA - 1.0)/GAMMA) - 1.0));
-        let p_t23 = pi*p_t2;
-
-        // Low pressure compressor
-        let eta = variables.lpc_efficiency;
-        let pi = variables.lpc_pressure_ratio;
-        let t_t25 = t_t23 * (1.0 + 1.0/eta * (pi.powf((GAMMA - 1.0)/GAMMA) - 1.0));
-        let p_t25 = pi*p_t23;
-        
+        let eta = Self::compressor_efficiency(&gas, &mut cycle, "Fan", poly.fan, t_t2, variables.fan_pressure_ratio, variables.fan_efficiency);
+        let t_t23 = gas.compressor_exit_temp(t_t2, variables.fan_pressure_ratio, eta);
+        let p_t23 = variables.fan_pressure_ratio*p_t2;
+
+        // Low pressure compressor (booster)
+        let (t_t25, p_t25) = if self.booster {
+            let eta = Self::compressor_efficiency(&gas, &mut cycle, "LPC", poly.lpc, t_t23, variables.lpc_pressure_ratio, variables.lpc_efficiency);
+            let t_t25 = gas.compressor_exit_temp(t_t23, variables.lpc_pressure_ratio, eta);
+            (t_t25, variables.lpc_pressure_ratio*p_t23)
+        } else {
+            (t_t23, p_t23)
+        };
+
+        // Intermediate pressure compressor
+        let (t_t26, p_t26) = match self.spools {
+            Spools::Two => (t_t25, p_t25),
+            Spools::Three => {
+                let eta = Self::compressor_efficiency(&gas, &mut cycle, "IPC", poly.ipc, t_t25, variables.ipc_pressure_ratio, variables.ipc_efficiency);
+                let t_t26 = gas.compressor_exit_temp(t_t25, variables.ipc_pressure_ratio, eta);
+                (t_t26, variables.ipc_pressure_ratio*p_t25)
+            },
+        };
+
         // High pressure compressor
-        let eta = variables.hpc_efficiency;
-        let pi = variables.hpc_pressure_ratio;
-        let t_t3 = t_t25 * (1.0 + 1.0/eta * (pi.powf((GAMMA - 1.0)/GAMMA) - 1.0));
-        let p_t3 = pi*p_t25;
+        let eta = Self::compressor_efficiency(&gas, &mut cycle, "HPC", poly.hpc, t_t26, variables.hpc_pressure_ratio, variables.hpc_efficiency);
+        let t_t3 = gas.compressor_exit_temp(t_t26, variables.hpc_pressure_ratio, eta);
+        let p_t3 = variables.hpc_pressure_ratio*p_t26;
+
+        // Core air left after the customer bleed, per unit of core air flow
+        let m3 = 1.0 - variables.customer_bleed_fraction;
 
         // Combustor
         let t_t4 = variables.hpt_inlet_temp;
-        let cp_fuel = variables.fuel_cp;
-        let f = cp_fuel * (t_t4 - t_t3) / variables.fuel_delta_h;
+        let f_burner = gas.burner_fuel(t_t3, t_t4, 0.0, 1.0, variables.fuel_delta_h);
         let p_t4 = p_t3;
+        cycle.fuel_air_ratio = f_burner;
 
-        // High pressure turbine
-        let eta = variables.hpt_efficiency;
-        let t_t45 = t_t4 - CP_AIR/cp_fuel/(1.0 + f) * (t_t3 - t_t25);
-        let p_t45 = p_t4 * (1.0 - 1.0/eta * (1.0 - t_t45/t_t4)).powf(GAMMA_FLUE/(GAMMA_FLUE - 1.0));
+        // Cooling air, as fractions of the HPC exit flow
+        let (b_ngv, b_rotor) = self.cooling_fractions(&gas, variables, t_t3, t_t4, f_burner);
 
-        // Low pressure turbine
-        let eta = variables.lpt_efficiency;
-        let beta = variables.fan_bypass;
-        let t_t5 = t_t45 - CP_AIR*(1.0 + beta)/cp_fuel/(1.0 + f) * (t_t23 - t_t2) - CP_AIR/cp_fuel/(1.0 + f) * (t_t25 - t_t23);
-        let p_t5 = p_t45 * (1.0 - 1.0/eta * (1.0 - t_t5/t_t45)).powf(GAMMA_FLUE/(GAMMA_FLUE - 1.0));
-
-        // Bypass nozzle
-        let p_t19 = p_t23;
-        let t_t19 = t_t23;
-        let m19 = (2.0 / (GAMMA - 1.0) * ((p_t19 / P0).powf((GAMMA - 1.0)/GAMMA) - 1.0)).sqrt();
-        let t19 = t_t19 / (1.0 + (GAMMA - 1.0)/2.0 * m19.powi(2));
-        let v19 = m19 * (GAMMA * R * t19).sqrt();
-
-        // Core nozzle
-        let p_t9 = p_t5;
-        let t_t9 = t_t5;
-        let m9 = (2.0 / (GAMMA_FLUE - 1.0) * ((p_t9 / P0).powf((GAMMA_FLUE - 1.0)/GAMMA_FLUE) - 1.0)).sqrt();
-        let t9 = t_t9 / (1.0 + (GAMMA_FLUE - 1.0)/2.0 * m9.powi(2));
-        let v9 = m9 * (GAMMA_FLUE * R * t9).sqrt();
-
-        // Thrust
-        let thrust = mdot * ((1.0 + f)/(1.0 + beta)*v9 + beta/(1.0 + beta) * v19);
-
-        // SFC
-        let sfc = f/(1.0 + beta) * mdot/thrust * 3600.0;
-
-        (thrust, sfc)
-    }
+        // Fuel burned per unit of core air flow
+        let f = m3*(1.0 - b_ngv - b_rotor)*f_burner;
+        let far = f/m3;
 
-    /// Compute the partial derivative of the thrust at a given value with respect to a given variable.
-    pub fn delta_thrust(&self, variables: Variables, var: VarSelector) -> f64 {
-        let dx: f64 = 1.0E-6;
+        // Air flow through the IPC and HPC, per unit of core air flow
+        let (m_ipc, m_hpc) = match self.bleed_station {
+            BleedStation::Lpc => (m3, m3),
+            BleedStation::Ipc => (1.0, m3),
+            BleedStation::Hpc => (1.0, 1.0),
+        };
 
-        let mut variables_high = variables;
-        *variables_high.get_reference(var) += dx;
+        // Work drawn by the compressors and offtakes on each spool, per unit of core air flow
+        let beta = variables.fan_bypass;
+        let mdot_core = mdot/(1.0 + beta);
+        let w_hp = m_hpc*gas.compressor_work(t_t26, t_t3) + 1000.0*variables.hp_power_offtake/mdot_core;
+        let w_ip = m_ipc*gas.compressor_work(t_t25, t_t26);
+        let w_fan = (1.0 + beta) * gas.compressor_work(t_t2, t_t23);
+        let w_lp = gas.compressor_work(t_t23, t_t25) + 1000.0*variables.lp_power_offtake/mdot_core + if self.geared {
+            // The booster runs at LPT speed; the fan power passes through the gearbox
+            w_fan / variables.gearbox_efficiency
+        } else {
+            w_fan
+        };
 
-        let mut variables_low = variables;
-        *variables_low.get_reference(var) -= dx;
+        // High pressure turbine stator, with NGV cooling air mixed in
+        let burner = gas.stream(m3*(1.0 - b_ngv - b_rotor)*(1.0 + f_burner), t_t4, p_t4, f_burner);
+        let rotor = if b_ngv > 0.0 {
+            gas.merge(burner, gas.stream(m3*b_ngv, t_t3, p_t3, 0.0))
+        } else {
+            burner
+        };
 
-        (self.analyze(variables_high).0 - self.analyze(variables_low).0)/(2.0*dx)
-    }
+        // High pressure turbine rotor, with rotor cooling air mixed in at the exit
+        let (t_t44, pi) = Self::turbine(&gas, &mut cycle, "HPT", poly.hpt, rotor.t_t, w_hp/rotor.mdot, variables.hpt_efficiency, rotor.far);
+        let hpt = gas.stream(rotor.mdot, t_t44, pi*rotor.p_t, rotor.far);
+        let hpt = if b_rotor > 0.0 {
+            gas.merge(hpt, gas.stream(m3*b_rotor, t_t3, p_t3, 0.0))
+        } else {
+            hpt
+        };
+        let (t_t45, p_t45) = (hpt.t_t, hpt.p_t);
+
+        // Intermediate pressure turbine
+        let (t_t48, p_t48) = match self.spools {
+            Spools::Two => (t_t45, p_t45),
+            Spools::Three => {
+                let (t_t48, pi) = Self::turbine(&gas, &mut cycle, "IPT", poly.ipt, t_t45, w_ip/(m3 + f), variables.ipt_efficiency, far);
+                (t_t48, pi*p_t45)
+            },
+        };
 
-    /// Compute the partial derivative of the SFC at a given value with respect to a given variable.
-    pub fn delta_sfc(&self, variables: Variables, var: VarSelector) -> f64 {
-        let dx = 0.001;
+        // Low pressure turbine
+        let (t_t5, pi) = Self::turbine(&gas, &mut cycle, "LPT", poly.lpt, t_t48, w_lp/(m3 + f), variables.lpt_efficiency, far);
+        let p_t5 = pi*p_t48;
 
-        let mut variables_high = variables;
-        *variables_high.get_reference(var) += dx;
+        cycle.station("2", t_t2, p_t2);
+        cycle.station("23", t_t23, p_t23);
+        if self.booster {
+            cycle.station("25", t_t25, p_t25);
+        }
+        if let Spools::Three = self.spools {
+            cycle.station("26", t_t26, p_t26);
+        }
+        cycle.station("3", t_t3, p_t3);
+        cycle.station("4", t_t4, p_t4);
+        if b_ngv > 0.0 {
+            cycle.station("41", rotor.t_t, rotor.p_t);
+        }
+        cycle.station("45", t_t45, p_t45);
+        if let Spools::Three = self.spools {
+            cycle.station("48", t_t48, p_t48);
+        }
+        cycle.station("5", t_t5, p_t5);
+
+        // Core stream leaving the LPT, per unit of inlet mass flow
+        let core = gas.stream((m3 + f)/(1.0 + beta), t_t5, p_t5, far);
+
+        // Bypass stream leaving the fan, per unit of inlet mass flow
+        let bypass = gas.stream(beta/(1.0 + beta), t_t23, p_t23, 0.0);
+
+        // Fuel burned per unit of inlet mass flow
+        let mut fuel = f/(1.0 + beta);
+
+        let thrust = match self.exhaust {
+            Exhaust::Separate => {
+                // Afterburner
+                let core = if self.afterburner {
+                    let (core, f_ab) = reheat(&gas, core, variables);
+                    cycle.station("7", core.t_t, core.p_t);
+                    cycle.fuel_air_ratio = cycle.fuel_air_ratio.max(core.far);
+                    fuel += f_ab;
+                    core
+                } else {
+                    core
+                };
+
+                // Bypass nozzle
+                let v19 = gas.exit_velocity(bypass, P0);
+
+                // Core nozzle
+                let v9 = gas.exit_velocity(core, P0);
+
+                cycle.station("9", core.t_t, core.p_t);
+                cycle.station("19", bypass.t_t, bypass.p_t);
+                cycle.jet("9", mdot * core.mdot, v9, gas.exit_density(core, P0));
+                cycle.jet("19", mdot * bypass.mdot, v19, gas.exit_density(bypass, P0));
+
+                mdot * (core.mdot*v9 + bypass.mdot*v19)
+            },
+            Exhaust::Mixed => {
+                // Mixer
+                let mixed = mix(core, bypass, variables.mixer_mach_number, variables.mixer_efficiency);
+                cycle.station("6A", mixed.t_t, mixed.p_t);
+
+                // Afterburner
+                let mixed = if self.afterburner {
+                    let (mixed, f_ab) = reheat(&gas, mixed, variables);
+                    cycle.station("7", mixed.t_t, mixed.p_t);
+                    cycle.fuel_air_ratio = cycle.fuel_air_ratio.max(mixed.far);
+                    fuel += f_ab;
+                    mixed
+                } else {
+                    mixed
+                };
+
+                // Common nozzle
+                let v9 = gas.exit_velocity(mixed, P0);
+
+                cycle.station("9", mixed.t_t, mixed.p_t);
+                cycle.jet("9", mdot * mixed.mdot, v9, gas.exit_density(mixed, P0));
+
+                mdot * mixed.mdot * v9
+            },
+        };
 
-        let mut variables_low = variables;
-        *variables_low.get_reference(var) -= dx;
+        cycle.thrust = thrust;
+        cycle.sfc = fuel * mdot/thrust * 3600.0;
+        cycle.mass_flow = mdot;
+        cycle.fuel_flow = fuel * mdot;
 
-        (self.analyze(variables_high).1 - self.analyze(variables_low).1)/(2.0*dx)
+        cycle
     }
 
-    /// Compute the gradient of the thrust value with respect to all variables.
-    pub fn thrust_gradient(&self, variables: Variables) -> Variables {
-        use VarSelector::*;
-
-        Variables {
-            inlet_mach_number: self.delta_thrust(variables, InletMachNumber),
-            inlet_diameter: self.delta_thrust(variables, InletDiameter),
-            inlet_efficiency: 0.0,
-            fan_pressure_ratio: self.delta_thrust(variables, FanPressureRatio),
-            fan_efficiency: 0.0,
-            fan_bypass: self.delta_thrust(variables, FanBypass),
-            lpc_pressure_ratio: self.delta_thrust(variables, LpcPressureRatio),
-            lpc_efficiency: 0.0,
-            hpc_pressure_ratio: self.delta_thrust(variables, HpcPressureRatio),
-            hpc_efficiency: 0.0,
-            hpc_discharge_temp: self.delta_thrust(variables, HpcDischargeTemp),
-            combustor_pressure_recovery: 0.0,
-            combustor_efficiency: 0.0,
-            hpt_inlet_temp: self.delta_thrust(variables, HptInletTemp),
-            hpt_efficiency: 0.0,
-            lpt_efficiency: 0.0,
-            bypass_pressure_recovery: 0.0,
-            fuel_delta_h: 0.0,
-            fuel_cp: 0.0,
-        }
+    /// Checks that this engine can be matched off-design.
+    fn check_off_design(&self) -> Result<(), String> {
+        OffDesign::check(*self)
     }
 
-    /// Compute the gradient of the SFC value with respect to all variables.
-    pub fn sfc_gradient(&self, variables: Variables) -> Variables {
-        use VarSelector::*;
-
-        Variables {
-            inlet_mach_number: self.delta_sfc(variables, InletMachNumber),
-            inlet_diameter: self.delta_sfc(variables, InletDiameter),
-            inlet_efficiency: 0.0,
-            fan_pressure_ratio: self.delta_sfc(variables, FanPressureRatio),
-            fan_efficiency: 0.0,
-            fan_bypass: self.delta_sfc(variables, FanBypass),
-            lpc_pressure_ratio: self.delta_sfc(variables, LpcPressureRatio),
-            lpc_efficiency: 0.0,
-            hpc_pressure_ratio: self.delta_sfc(variables, HpcPressureRatio),
-            hpc_efficiency: 0.0,
-            hpc_discharge_temp: self.delta_sfc(variables, HpcDischargeTemp),
-            combustor_pressure_recovery: 0.0,
-            combustor_efficiency: 0.0,
-            hpt_inlet_temp: self.delta_sfc(variables, HptInletTemp),
-            hpt_efficiency: 0.0,
-            lpt_efficiency: 0.0,
-            bypass_pressure_recovery: 0.0,
-            fuel_delta_h: 0.0,
-            fuel_cp: 0.0,
-        }
+    /// Matches this engine, sized by `variables`, to its component maps at a flight condition.
+    fn off_design(&self, variables: Variables, maps: &Maps, condition: Condition) -> Option<Cycle> {
+        OffDesign::new(*self, variables, maps).ok()?.solve(condition)
     }
 
-    /// Perform a gradient ascent optimization step for maximizing thrust.
-    fn step_thrust_optimization(&self, variables: Variables) -> Variables {
-        let step = self.thrust_gradient(variables).mult(OPTIMIZATION_RATE);
-        
-        variables + step
+    /// Scales the HPC map of this engine to its design point.
+    fn compressor_map(&self, variables: Variables, maps: &Maps) -> Option<ScaledMap> {
+        OffDesign::new(*self, variables, maps).ok().map(|offdesign| offdesign.hpc_map())
     }
 
-    /// Perform a gradient ascent optimization step for minimizing SFC.
-    fn step_sfc_optimization(&self, mut variables: Variables) -> Variables {
-        let step = self.sfc_gradient(variables).mult(OPTIMIZATION_RATE);
+    /// Estimates the dry weight and dimensions of this engine from its stage counts and annulus sizes.
+    fn size_cycle(&self, variables: Variables, cycle: &Cycle) -> Option<Size> {
+        let mdot_core = cycle.mass_flow / (1.0 + variables.fan_bypass);
 
-        variables.hpc_pressure_ratio = OPR / variables.lpc_pressure_ratio / variables.fan_pressure_ratio;
-        
-        variables + step.mult(-1.0)
-    }
+        let mut modules = vec![Module::fan(variables.inlet_diameter, variables.fan_pressure_ratio)];
+
+        // Compressors
+        let mut inlet = station(cycle, "23")?;
+        if self.booster {
+            let exit = station(cycle, "25")?;
+            modules.push(Module::compressor("LPC", mdot_core, inlet, exit, LPC_STAGE_PRESSURE_RATIO));
+            inlet = exit;
+        }
+        if let Spools::Three = self.spools {
+            let exit = station(cycle, "26")?;
+            modules.push(Module::compressor("IPC", mdot_core, inlet, exit, IPC_STAGE_PRESSURE_RATIO));
+            inlet = exit;
+        }
+        let hpc = Module::compressor("HPC", mdot_core, inlet, station(cycle, "3")?, HPC_STAGE_PRESSURE_RATIO);
+        modules.push(hpc);
+        modules.push(Module::combustor(hpc.diameter));
+
+        // Turbines
+        let inlet = station(cycle, "41").or(station(cycle, "4"))?;
+        let mut exit = station(cycle, "45")?;
+        modules.push(Module::turbine("HPT", mdot_core, inlet, exit, HPT_BLADE_SPEED));
+        if let Spools::Three = self.spools {
+            let inlet = exit;
+            exit = station(cycle, "48")?;
+            modules.push(Module::turbine("IPT", mdot_core, inlet, exit, IPT_BLADE_SPEED));
+        }
+
+        // A geared LPT spins `gear_ratio` times faster than the fan, so it needs fewer stages
+        let blade_speed = if self.geared {
+            (LPT_BLADE_SPEED * variables.gear_ratio).min(MAX_LPT_BLADE_SPEED)
+        } else {
+            LPT_BLADE_SPEED
+        };
+        let lpt = Module::turbine("LPT", mdot_core, exit, station(cycle, "5")?, blade_speed);
+        modules.push(lpt);
 
-    /// Perform gradient ascent optimization to maximize thrust.
-    pub fn optimize_thrust(&self, mut variables: Variables, n: usize) -> Variables {
-        for _ in 0..n {
-            variables = self.step_thrust_optimization(variables);
+        if self.afterburner {
+            modules.push(Module::afterburner(lpt.diameter));
+        }
+        if self.geared {
+            let (t_t2, _) = station(cycle, "2")?;
+            let (t_t23, _) = station(cycle, "23")?;
+            modules.push(Module::gearbox(cycle.mass_flow * CP_AIR * (t_t23 - t_t2) / 1000.0));
         }
 
-        variables
+        Some (Size::new(modules))
     }
 
-    /// Perform gradient ascent optimization to minimize SFC.
-    pub fn optimize_sfc(&self, mut variables: Variables) -> Variables {
-        #[allow(unused_assignments)]
-        let (mut t, mut sfc) = (MIN_THRUST, 0.0);
-        let mut i = 0;
-        while t >= MIN_THRUST {
-            variables = self.step_sfc_optimization(variables);
-            (t, sfc) = self.analyze(variables);
-            
-            if i % 1_000_000 == 0 {
-                println!("Thrust: {:.8} N | SFC: {:.8} kg/N-hr", t, sfc);
-            }
-
-            i += 1;
+    /// Estimates the short fan cowl and exposed core cowl of this engine from its size.
+    fn nacelle(&self, variables: Variables) -> Option<Nacelle> {
+        Some (Nacelle::separate(variables.inlet_diameter, &self.size(variables)?))
+    }
+
+    /// Computes the overall pressure ratio of this engine.
+    fn overall_pressure_ratio(&self, variables: Variables) -> f64 {
+        let mut opr = variables.fan_pressure_ratio * variables.hpc_pressure_ratio;
+        if self.booster {
+            opr *= variables.lpc_pressure_ratio;
+        }
+        if let Spools::Three = self.spools {
+            opr *= variables.ipc_pressure_ratio;
         }
 
-        println!("\nFinal thrust: {:.8} lbf\nFinal SFC: {:.8} lbm/lbf-hr", t*0.224809, sfc*2.20462262185/0.224809);
-        variables
+        opr
     }
 
-    /// Plot thrust as a function of one variable.
-    pub fn plot_thrust(
-        &self,
-        selected: VarSelector,
-        left: f64,
-        right: f64,
-        vars: Variables,
-    ) {
-        let n: usize = 1000;
-
-        let thrust = |input| {
-            let mut variables = vars;
-            *variables.get_reference(selected) = input;
-            Turbofan::new().analyze(variables).0
-        };
+    /// Names this engine.
+    fn name(&self) -> String {
+        "Turbofan".to_string()
+    }
 
-        let varname: String = selected.into();
-
-        plot(
-            thrust,
-            left,
-            right,
-            n,
-            &varname,
-            "Thrust (N)",
-            &format!("{} Thrust Plot.png", varname),
-        );
+    /// Gives the fuel burned by this engine.
+    fn fuel(&self) -> Fuel {
+        self.fuel
     }
+}
 
-    /// Plot specific fuel consumption as a function of one variable.
-    pub fn plot_sfc(
-        &self,
-        selected: VarSelector,
-        left: f64,
-        right: f64,
-        vars: Variables,
-    ) {
-        let n: usize = 1000;
-
-        let sfc = |input| {
-            let mut variables = vars;
-            *variables.get_reference(selected) = input;
-            Turbofan::new().analyze(variables).1
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let varname: String = selected.into();
-
-        plot(
-            sfc,
-            left,
-            right,
-            n,
-            &varname,
-            "SFC (kg/N-hr)",
-            &format!("{} SFC Plot.png", varname),
-        );
+    #[test]
+    fn metal_temperature_cooling_converges() {
+        let engine = Turbofan {
+            cooling: Cooling::Metal,
+            ..Turbofan::new()
+        };
+        let variables = VANILLA_PLUS;
+        let gas = Gas::new(engine.gas, engine.fuel, variables);
+
+        let cycle = engine.cycle(variables);
+        let temp = |name: &str| cycle.stations.iter().find(|s| s.name == name).unwrap().total_temp;
+        let (t_t3, t_t4, f) = (temp("3"), temp("4"), cycle.fuel_air_ratio);
+
+        let (ngv, rotor) = engine.cooling_fractions(&gas, variables, t_t3, t_t4, f);
+
+        // One more pass of the fixed-point loop leaves the rotor cooling air unchanged
+        let burner = gas.stream((1.0 - ngv - rotor)*(1.0 + f), t_t4, 1.0, f);
+        let t_t41 = gas.merge(burner, gas.stream(ngv, t_t3, 1.0, 0.0)).t_t;
+        let e = ((t_t41 - variables.blade_metal_temp) / (t_t41 - t_t3)).clamp(0.0, 0.9);
+        let next = variables.cooling_flow_factor * e/(1.0 - e);
+        assert!((next - rotor).abs() <= 1.0E-9, "rotor cooling {} did not converge to {}", rotor, next);
+
+        // Neither blade row needs the limiting effectiveness
+        for (name, fraction) in [("NGV", ngv), ("rotor", rotor)] {
+            let effectiveness = fraction / (variables.cooling_flow_factor + fraction);
+            assert!(effectiveness > 0.0 && effectiveness < 0.9, "{} cooling effectiveness is {}", name, effectiveness);
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,132 @@
+//! Computes the performance of a turbojet.
+
+use crate::{
+    constants::*,
+    flow::{
+        inlet_mass_flow,
+        reheat,
+        Stream,
+    },
+    gas::Gas,
+    Cycle,
+    Engine,
+    Fuel,
+    GasModel,
+    Variables,
+};
+
+/// Computes the gas generator (LPC, HPC, combustor, HPT and LPT) of a core engine.
+///
+/// Returns the inlet mass flow rate and the stream leaving the LPT per unit of inlet mass flow.
+pub fn gas_generator(gas: &Gas, variables: Variables, cycle: &mut Cycle) -> (f64, Stream) {
+    let t_t2 = T0;
+    let p_t2 = P0;
+
+    // Compute mass flow rate
+    let mdot = inlet_mass_flow(variables);
+
+    // Low pressure compressor
+    let t_t25 = gas.compressor_exit_temp(t_t2, variables.lpc_pressure_ratio, variables.lpc_efficiency);
+    let p_t25 = variables.lpc_pressure_ratio*p_t2;
+
+    // High pressure compressor
+    let t_t3 = gas.compressor_exit_temp(t_t25, variables.hpc_pressure_ratio, variables.hpc_efficiency);
+    let p_t3 = variables.hpc_pressure_ratio*p_t25;
+
+    // Combustor
+    let t_t4 = variables.hpt_inlet_temp;
+    let f = gas.burner_fuel(t_t3, t_t4, 0.0, 1.0, variables.fuel_delta_h);
+    let p_t4 = p_t3;
+    cycle.fuel_air_ratio = f;
+
+    // High pressure turbine
+    let (t_t45, pi) = gas.turbine(t_t4, gas.compressor_work(t_t25, t_t3)/(1.0 + f), variables.hpt_efficiency, f);
+    let p_t45 = pi*p_t4;
+
+    // Low pressure turbine
+    let (t_t5, pi) = gas.turbine(t_t45, gas.compressor_work(t_t2, t_t25)/(1.0 + f), variables.lpt_efficiency, f);
+    let p_t5 = pi*p_t45;
+
+    cycle.station("2", t_t2, p_t2);
+    cycle.station("25", t_t25, p_t25);
+    cycle.station("3", t_t3, p_t3);
+    cycle.station("4", t_t4, p_t4);
+    cycle.station("45", t_t45, p_t45);
+    cycle.station("5", t_t5, p_t5);
+
+    let core = gas.stream(1.0 + f, t_t5, p_t5, f);
+
+    (mdot, core)
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Constructs a two-spool turbojet.
+pub struct Turbojet {
+    /// Whether the afterburner is lit
+    pub afterburner: bool,
+
+    /// Gas property model
+    pub gas: GasModel,
+
+    /// Fuel burned
+    pub fuel: Fuel,
+}
+
+impl Turbojet {
+    /// Constructs a new turbojet with no afterburner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Engine for Turbojet {
+    /// Computes the thermodynamic cycle of this engine.
+    ///
+    /// The fan variables are ignored.  With the afterburner lit, this gives the
+    /// wet thrust and wet SFC.
+    fn cycle(&self, variables: Variables) -> Cycle {
+        let mut cycle = Cycle::default();
+        let gas = Gas::new(self.gas, self.fuel, variables);
+
+        let (mdot, core) = gas_generator(&gas, variables, &mut cycle);
+        let mut fuel = core.mdot - 1.0;
+
+        // Afterburner
+        let core = if self.afterburner {
+            let (core, f_ab) = reheat(&gas, core, variables);
+            cycle.station("7", core.t_t, core.p_t);
+            cycle.fuel_air_ratio = cycle.fuel_air_ratio.max(core.far);
+            fuel += f_ab;
+            core
+        } else {
+            core
+        };
+
+        // Nozzle
+        let v9 = gas.exit_velocity(core, P0);
+        cycle.station("9", core.t_t, core.p_t);
+        cycle.jet("9", mdot * core.mdot, v9, gas.exit_density(core, P0));
+
+        cycle.thrust = mdot * core.mdot * v9;
+        cycle.sfc = fuel * mdot/cycle.thrust * 3600.0;
+        cycle.mass_flow = mdot;
+        cycle.fuel_flow = fuel * mdot;
+
+        cycle
+    }
+
+    /// Computes the overall pressure ratio of this engine.
+    fn overall_pressure_ratio(&self, variables: Variables) -> f64 {
+        variables.lpc_pressure_ratio * variables.hpc_pressure_ratio
+    }
+
+    /// Names this engine.
+    fn name(&self) -> String {
+        "Turbojet".to_string()
+    }
+
+    /// Gives the fuel burned by this engine.
+    fn fuel(&self) -> Fuel {
+        self.fuel
+    }
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+//! Computes the performance of a turboprop.
+
+use crate::{
+    constants::*,
+    gas::Gas,
+    turbojet::gas_generator,
+    turboshaft::power_turbine,
+    Cycle,
+    Engine,
+    Fuel,
+    GasModel,
+    Variables,
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Constructs a turboprop with a free power turbine driving the propeller.
+pub struct Turboprop {
+    /// Gas property model
+    pub gas: GasModel,
+
+    /// Fuel burned
+    pub fuel: Fuel,
+}
+
+impl Turboprop {
+    /// Constructs a new turboprop.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Engine for Turboprop {
+    /// Computes the thermodynamic cycle of this engine.
+    ///
+    /// The fan variables are ignored.  The thrust is the equivalent thrust, i.e.
+    /// the static propeller thrust from actuator disk theory plus the residual jet thrust.
+    fn cycle(&self, variables: Variables) -> Cycle {
+        let mut cycle = Cycle::default();
+        let gas = Gas::new(self.gas, self.fuel, variables);
+
+        let (mdot, core) = gas_generator(&gas, variables, &mut cycle);
+        let fuel = core.mdot - 1.0;
+
+        // Power turbine
+        let (core, work) = power_turbine(&gas, core, variables, &mut cycle);
+        let power = mdot * work;
+
+        // Propeller
+        let rho = P0 / (R * T0);
+        let area = 0.25 * PI * variables.propeller_diameter.powi(2);
+        let thrust_prop = (2.0 * rho * area * (variables.propeller_efficiency * power).powi(2)).cbrt();
+
+        // Nozzle
+        let v9 = gas.exit_velocity(core, P0);
+        cycle.station("9", core.t_t, core.p_t);
+        cycle.jet("9", mdot * core.mdot, v9, gas.exit_density(core, P0));
+
+        cycle.thrust = thrust_prop + mdot * core.mdot * v9;
+        cycle.sfc = fuel * mdot/cycle.thrust * 3600.0;
+        cycle.mass_flow = mdot;
+        cycle.fuel_flow = fuel * mdot;
+        cycle.shaft_power = power;
+
+        cycle
+    }
+
+    /// Computes the overall pressure ratio of this engine.
+    fn overall_pressure_ratio(&self, variables: Variables) -> f64 {
+        variables.lpc_pressure_ratio * variables.hpc_pressure_ratio
+    }
+
+    /// Names this engine.
+    fn name(&self) -> String {
+        "Turboprop".to_string()
+    }
+
+    /// Gives the fuel burned by this engine.
+    fn fuel(&self) -> Fuel {
+        self.fuel
+    }
+
+    /// Labels the equivalent thrust output of this engine.
+    fn thrust_label(&self) -> &'static str {
+        "Equivalent Thrust (N)"
+    }
+}
\ No newline at end of file
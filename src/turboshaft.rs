@@ -0,0 +1,117 @@
+//! Computes the performance of a turboshaft.
+
+use crate::{
+    constants::*,
+    flow::Stream,
+    gas::Gas,
+    turbojet::gas_generator,
+    Cycle,
+    Engine,
+    Fuel,
+    GasModel,
+    Requirements,
+    Variables,
+};
+
+/// Expands a stream through a free power turbine down to the nozzle pressure ratio.
+///
+/// Returns the stream leaving the power turbine and the shaft work per unit of inlet mass flow (in J/kg).
+pub fn power_turbine(gas: &Gas, stream: Stream, variables: Variables, cycle: &mut Cycle) -> (Stream, f64) {
+    let p_t = variables.nozzle_pressure_ratio * P0;
+    let t_t = gas.turbine_exit_temp(stream.t_t, p_t/stream.p_t, variables.power_turbine_efficiency, stream.far);
+    let work = stream.mdot * (gas.enthalpy(stream.t_t, stream.far) - gas.enthalpy(t_t, stream.far));
+
+    cycle.station("49", t_t, p_t);
+
+    let stream = gas.stream(stream.mdot, t_t, p_t, stream.far);
+
+    (stream, work)
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Constructs a turboshaft with a free power turbine.
+pub struct Turboshaft {
+    /// Gas property model
+    pub gas: GasModel,
+
+    /// Fuel burned
+    pub fuel: Fuel,
+}
+
+impl Turboshaft {
+    /// Constructs a new turboshaft.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Engine for Turboshaft {
+    /// Computes the thermodynamic cycle of this engine.
+    ///
+    /// The fan and propeller variables are ignored.  The thrust is that of the residual jet.
+    fn cycle(&self, variables: Variables) -> Cycle {
+        let mut cycle = Cycle::default();
+        let gas = Gas::new(self.gas, self.fuel, variables);
+
+        let (mdot, core) = gas_generator(&gas, variables, &mut cycle);
+        let fuel = core.mdot - 1.0;
+
+        // Power turbine
+        let (core, work) = power_turbine(&gas, core, variables, &mut cycle);
+
+        // Nozzle
+        let v9 = gas.exit_velocity(core, P0);
+        cycle.station("9", core.t_t, core.p_t);
+        cycle.jet("9", mdot * core.mdot, v9, gas.exit_density(core, P0));
+
+        cycle.thrust = mdot * core.mdot * v9;
+        cycle.sfc = fuel * mdot/cycle.thrust * 3600.0;
+        cycle.mass_flow = mdot;
+        cycle.fuel_flow = fuel * mdot;
+        cycle.shaft_power = mdot * work;
+
+        cycle
+    }
+
+    /// Computes the shaft power (in kW) and power specific fuel consumption (in kg/kW-hr) of this engine.
+    fn analyze_cycle(&self, cycle: &Cycle) -> (f64, f64) {
+        let power = cycle.shaft_power / 1000.0;
+
+        (power, cycle.fuel_flow * 3600.0 / power)
+    }
+
+    /// Computes the overall pressure ratio of this engine.
+    fn overall_pressure_ratio(&self, variables: Variables) -> f64 {
+        variables.lpc_pressure_ratio * variables.hpc_pressure_ratio
+    }
+
+    /// Names this engine.
+    fn name(&self) -> String {
+        "Turboshaft".to_string()
+    }
+
+    /// Gives the fuel burned by this engine.
+    fn fuel(&self) -> Fuel {
+        self.fuel
+    }
+
+    /// Labels the shaft power output of this engine.
+    fn thrust_label(&self) -> &'static str {
+        "Shaft Power (kW)"
+    }
+
+    /// Labels the power specific fuel consumption output of this engine.
+    fn sfc_label(&self) -> &'static str {
+        "PSFC (kg/kW-hr)"
+    }
+
+    /// Formats the shaft power and PSFC outputs of this engine.
+    fn performance(&self, power: f64, psfc: f64) -> String {
+        format!("{}: {:.6}\n{}: {:.6}\n", self.thrust_label(), power, self.sfc_label(), psfc)
+    }
+
+    /// Gives the minimum allowable shaft power (in kW) of this engine under a set of requirements.
+    fn min_thrust(&self, requirements: &Requirements) -> f64 {
+        requirements.shaft_power
+    }
+}
\ No newline at end of file
@@ -0,0 +1,223 @@
+//! Propagates uncertainty in the design variables to thrust and SFC by Monte Carlo sampling.
+//!
+//! Each uncertain variable is given a distribution with `--vary`, for example
+//! `--vary fan_efficiency=normal:0.92,0.01`.  Samples are drawn independently
+//! from a seeded generator, clamped to the variable bounds, and run through the
+//! design-point cycle.  Clamping piles up probability at the bounds and biases
+//! the statistics, so the number of clamped samples is reported.
+
+use std::{
+    fmt::{
+        self,
+        Display,
+        Formatter,
+    },
+    process,
+};
+
+use crate::{
+    parallel,
+    plot_histogram,
+    random::{
+        Distribution,
+        Random,
+    },
+    Bounds,
+    Engine,
+    Variables,
+    VarSelector,
+};
+
+/// Percentiles reported for each output.
+const PERCENTILES: [f64; 5] = [5.0, 25.0, 50.0, 75.0, 95.0];
+
+/// Number of bins in each histogram.
+const BINS: usize = 40;
+
+#[derive(Clone, Debug)]
+/// Holds the uncertain variables and the sampling settings.
+pub struct Uncertainty {
+    /// Uncertain variables and their distributions
+    pub inputs: Vec<(VarSelector, Distribution)>,
+
+    /// Number of samples
+    pub samples: usize,
+
+    /// Seed of the random number generator
+    pub seed: u64,
+}
+
+impl Default for Uncertainty {
+    fn default() -> Self {
+        Self {
+            inputs: Vec::new(),
+            samples: 1000,
+            seed: 0,
+        }
+    }
+}
+
+impl Uncertainty {
+    /// Adds an uncertain variable from a specification of the form `variable=distribution`.
+    pub fn vary(&mut self, spec: &str) {
+        let (name, distribution) = spec.split_once('=').unwrap_or_else(|| {
+            println!("[FATAL] Could not parse {}.  Use `variable=distribution`.", spec);
+            process::exit(0);
+        });
+
+        let var: VarSelector = name.to_string().into();
+        self.inputs.push((var, distribution.into()));
+    }
+
+    /// Samples the uncertain variables around a design and computes the thrust and SFC of each sample.
+    pub fn propagate(&self, engine: &dyn Engine, variables: Variables, bounds: &Bounds) -> Dispersion {
+        let mut random = Random::new(self.seed);
+        let mut clamped = 0;
+        let samples = (0..self.samples).map(|_| {
+            let mut sample = variables;
+            for (var, distribution) in &self.inputs {
+                *sample.get_reference(*var) = distribution.sample(&mut random);
+            }
+
+            let mut inside = bounds.clamp(sample);
+            if self.inputs.iter().any(|&(var, _)| *inside.get_reference(var) != *sample.get_reference(var)) {
+                clamped += 1;
+            }
+            inside
+        }).collect::<Vec<Variables>>();
+
+        let (thrust, sfc): (Vec<f64>, Vec<f64>) = parallel::map(&samples, |&sample| engine.analyze(sample))
+            .into_iter()
+            .filter(|(t, s)| t.is_finite() && s.is_finite())
+            .unzip();
+
+        Dispersion {
+            failed: self.samples - thrust.len(),
+            clamped,
+            thrust: Statistics::new(thrust, engine.thrust_label()),
+            sfc: Statistics::new(sfc, engine.sfc_label()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Holds the sampled values and summary statistics of one output.
+pub struct Statistics {
+    /// Output label
+    pub label: &'static str,
+
+    /// Sampled values, in ascending order
+    pub values: Vec<f64>,
+
+    /// Sample mean
+    pub mean: f64,
+
+    /// Sample standard deviation
+    pub std_dev: f64,
+}
+
+impl Statistics {
+    /// Computes the summary statistics of a set of samples.
+    pub fn new(mut values: Vec<f64>, label: &'static str) -> Self {
+        values.sort_by(f64::total_cmp);
+
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+
+        Self {
+            label,
+            values,
+            mean,
+            std_dev,
+        }
+    }
+
+    /// Computes a percentile (from 0 to 100) by linear interpolation between samples.
+    pub fn percentile(&self, percent: f64) -> f64 {
+        let position = percent/100.0 * (self.values.len() - 1) as f64;
+        let i = position.floor() as usize;
+        let j = (i + 1).min(self.values.len() - 1);
+
+        self.values[i] + (position - i as f64) * (self.values[j] - self.values[i])
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Holds the spread of thrust and SFC over a set of samples.
+pub struct Dispersion {
+    /// Thrust statistics
+    pub thrust: Statistics,
+
+    /// SFC statistics
+    pub sfc: Statistics,
+
+    /// Number of samples whose cycle could not be computed
+    pub failed: usize,
+
+    /// Number of samples clamped to the variable bounds
+    pub clamped: usize,
+}
+
+impl Dispersion {
+    /// Plots histograms of thrust and SFC.
+    pub fn plot_histograms(&self, name: &str) {
+        for statistics in [&self.thrust, &self.sfc] {
+            let short = statistics.label.split(" (").next().unwrap_or(statistics.label);
+            plot_histogram(
+                &statistics.values,
+                BINS,
+                statistics.label,
+                &format!("{} {} Histogram.png", name, short),
+            );
+        }
+    }
+}
+
+impl Display for Dispersion {
+    /// Format this data structure.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+
+        output.push_str(&format!("{:<16}{:>24}{:>24}\n", "", self.thrust.label, self.sfc.label));
+        output.push_str(&format!("{:<16}{:>24.3}{:>24.8}\n", "Mean", self.thrust.mean, self.sfc.mean));
+        output.push_str(&format!("{:<16}{:>24.3}{:>24.8}\n", "Std. deviation", self.thrust.std_dev, self.sfc.std_dev));
+        for percent in PERCENTILES {
+            output.push_str(&format!("{:<16}{:>24.3}{:>24.8}\n", format!("P{:.0}", percent), self.thrust.percentile(percent), self.sfc.percentile(percent)));
+        }
+
+        output.push_str(&format!("\nSamples: {}\n", self.thrust.values.len()));
+        if self.failed > 0 {
+            output.push_str(&format!("Failed samples: {}\n", self.failed));
+        }
+        if self.clamped > 0 {
+            output.push_str(&format!("Samples clamped to the variable bounds: {}\n", self.clamped));
+        }
+
+        write!(f, "{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::VANILLA_PLUS,
+        Turbofan,
+    };
+
+    #[test]
+    fn counts_clamped_samples() {
+        let mut uncertainty = Uncertainty {
+            samples: 100,
+            ..Uncertainty::default()
+        };
+
+        uncertainty.vary("fan_bypass=uniform:6,8");
+        assert_eq!(uncertainty.propagate(&Turbofan::new(), VANILLA_PLUS, &Bounds::default()).clamped, 0);
+
+        uncertainty.vary("fan_efficiency=normal:0.99,0.05");
+        let clamped = uncertainty.propagate(&Turbofan::new(), VANILLA_PLUS, &Bounds::default()).clamped;
+        assert!(clamped > 0 && clamped < 100, "{} samples clamped", clamped);
+    }
+}
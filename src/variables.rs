@@ -6,9 +6,13 @@ use std::{
         Formatter,
         Result,
     },
+    fs,
     ops::{
         Add,
     },
+    path::Path,
+    process,
+    str::FromStr,
 };
 
 use crate::{
@@ -37,12 +41,24 @@ pub struct Variables {
     /// Fan bypass ratio
     pub fan_bypass: f64,
 
+    /// Fan gearbox reduction ratio (LPT speed over fan speed)
+    pub gear_ratio: f64,
+
+    /// Fan gearbox mechanical efficiency
+    pub gearbox_efficiency: f64,
+
     /// Low pressure compressor pressure ratio
     pub lpc_pressure_ratio: f64,
 
     /// Low pressure compressor adiabatic efficiency
     pub lpc_efficiency: f64,
 
+    /// Intermediate pressure compressor pressure ratio
+    pub ipc_pressure_ratio: f64,
+
+    /// Intermediate pressure compressor adiabatic efficiency
+    pub ipc_efficiency: f64,
+
     /// High pressure compressor pressure ratio
     pub hpc_pressure_ratio: f64,
 
@@ -64,6 +80,9 @@ pub struct Variables {
     /// High pressure turbine efficiency
     pub hpt_efficiency: f64,
 
+    /// Intermediate pressure turbine efficiency
+    pub ipt_efficiency: f64,
+
     /// Low pressure turbine efficiency
     pub lpt_efficiency: f64,
 
@@ -75,11 +94,79 @@ pub struct Variables {
 
     /// Fuel specific heat capacity at constant pressure
     pub fuel_cp: f64,
+
+    /// Mach number of the lower-pressure stream at mixer entry
+    pub mixer_mach_number: f64,
+
+    /// Mixer total pressure efficiency
+    pub mixer_efficiency: f64,
+
+    /// Afterburner exit temperature (in K)
+    pub afterburner_exit_temp: f64,
+
+    /// Afterburner pressure recovery coefficient
+    pub afterburner_pressure_recovery: f64,
+
+    /// Afterburner combustion efficiency
+    pub afterburner_efficiency: f64,
+
+    /// Free power turbine efficiency
+    pub power_turbine_efficiency: f64,
+
+    /// Ratio of power turbine exit total pressure to ambient pressure
+    pub nozzle_pressure_ratio: f64,
+
+    /// Propeller efficiency (static figure of merit)
+    pub propeller_efficiency: f64,
+
+    /// Propeller diameter (in m)
+    pub propeller_diameter: f64,
+
+    /// Flight Mach number
+    pub flight_mach_number: f64,
+
+    /// Ramjet burner entry Mach number
+    pub burner_mach_number: f64,
+
+    /// Ramjet burner exit temperature (in K)
+    pub burner_exit_temp: f64,
+
+    /// HPT stator (NGV) cooling air, as a fraction of HPC exit flow
+    pub ngv_cooling_fraction: f64,
+
+    /// HPT rotor cooling air, as a fraction of HPC exit flow
+    pub rotor_cooling_fraction: f64,
+
+    /// Allowable HPT blade metal temperature (in K)
+    pub blade_metal_temp: f64,
+
+    /// Cooling technology factor relating cooling flow to cooling effectiveness
+    pub cooling_flow_factor: f64,
+
+    /// Customer bleed air, as a fraction of core air flow
+    pub customer_bleed_fraction: f64,
+
+    /// Shaft power extracted from the high pressure spool (in kW)
+    pub hp_power_offtake: f64,
+
+    /// Shaft power extracted from the low pressure spool (in kW)
+    pub lp_power_offtake: f64,
 }
 
 use VarSelector::*;
 
 impl Variables {
+    /// Reads a design from a `.jetlab` file.
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok (text) => text.as_str().into(),
+            Err (_) => {
+                println!("[FATAL] Could not read design {}", path.display());
+                process::exit(0);
+            },
+        }
+    }
+
     /// Computes inlet area (in m2).
     pub fn inlet_area(&self) -> f64 {
         0.25 * PI * self.inlet_diameter.powi(2)
@@ -94,8 +181,12 @@ impl Variables {
             FanPressureRatio            => &mut self.fan_pressure_ratio,
             FanEfficiency               => &mut self.fan_efficiency,
             FanBypass                   => &mut self.fan_bypass,
+            GearRatio                   => &mut self.gear_ratio,
+            GearboxEfficiency           => &mut self.gearbox_efficiency,
             LpcPressureRatio            => &mut self.lpc_pressure_ratio,
             LpcEfficiency               => &mut self.lpc_efficiency,
+            IpcPressureRatio            => &mut self.ipc_pressure_ratio,
+            IpcEfficiency               => &mut self.ipc_efficiency,
             HpcPressureRatio            => &mut self.hpc_pressure_ratio,
             HpcEfficiency               => &mut self.hpc_efficiency,
             HpcDischargeTemp            => &mut self.hpc_discharge_temp,
@@ -103,10 +194,30 @@ impl Variables {
             CombustorEfficiency         => &mut self.combustor_efficiency,
             HptInletTemp                => &mut self.hpt_inlet_temp,
             HptEfficiency               => &mut self.hpt_efficiency,
+            IptEfficiency               => &mut self.ipt_efficiency,
             LptEfficiency               => &mut self.lpt_efficiency,
             BypassPressureRecovery      => &mut self.bypass_pressure_recovery,
             FuelDeltaH                  => &mut self.fuel_delta_h,
             FuelCp                      => &mut self.fuel_cp,
+            MixerMachNumber             => &mut self.mixer_mach_number,
+            MixerEfficiency             => &mut self.mixer_efficiency,
+            AfterburnerExitTemp         => &mut self.afterburner_exit_temp,
+            AfterburnerPressureRecovery => &mut self.afterburner_pressure_recovery,
+            AfterburnerEfficiency       => &mut self.afterburner_efficiency,
+            PowerTurbineEfficiency      => &mut self.power_turbine_efficiency,
+            NozzlePressureRatio         => &mut self.nozzle_pressure_ratio,
+            PropellerEfficiency         => &mut self.propeller_efficiency,
+            PropellerDiameter           => &mut self.propeller_diameter,
+            FlightMachNumber            => &mut self.flight_mach_number,
+            BurnerMachNumber            => &mut self.burner_mach_number,
+            BurnerExitTemp              => &mut self.burner_exit_temp,
+            NgvCoolingFraction          => &mut self.ngv_cooling_fraction,
+            RotorCoolingFraction        => &mut self.rotor_cooling_fraction,
+            BladeMetalTemp              => &mut self.blade_metal_temp,
+            CoolingFlowFactor           => &mut self.cooling_flow_factor,
+            CustomerBleedFraction       => &mut self.customer_bleed_fraction,
+            HpPowerOfftake              => &mut self.hp_power_offtake,
+            LpPowerOfftake              => &mut self.lp_power_offtake,
         }
     }
 
@@ -119,8 +230,12 @@ impl Variables {
             fan_pressure_ratio: k*self.fan_pressure_ratio,
             fan_efficiency: k*self.fan_efficiency,
             fan_bypass: k*self.fan_bypass,
+            gear_ratio: k*self.gear_ratio,
+            gearbox_efficiency: k*self.gearbox_efficiency,
             lpc_pressure_ratio: k*self.lpc_pressure_ratio,
             lpc_efficiency: k*self.lpc_efficiency,
+            ipc_pressure_ratio: k*self.ipc_pressure_ratio,
+            ipc_efficiency: k*self.ipc_efficiency,
             hpc_pressure_ratio: k*self.hpc_pressure_ratio,
             hpc_efficiency: k*self.hpc_efficiency,
             hpc_discharge_temp: k*self.hpc_discharge_temp,
@@ -128,14 +243,76 @@ impl Variables {
             combustor_efficiency: k*self.combustor_efficiency,
             hpt_inlet_temp: k*self.hpt_inlet_temp,
             hpt_efficiency: k*self.hpt_efficiency,
+            ipt_efficiency: k*self.ipt_efficiency,
             lpt_efficiency: k*self.lpt_efficiency,
             bypass_pressure_recovery: k*self.bypass_pressure_recovery,
             fuel_delta_h: k*self.fuel_delta_h,
             fuel_cp: k*self.fuel_cp,
+            mixer_mach_number: k*self.mixer_mach_number,
+            mixer_efficiency: k*self.mixer_efficiency,
+            afterburner_exit_temp: k*self.afterburner_exit_temp,
+            afterburner_pressure_recovery: k*self.afterburner_pressure_recovery,
+            afterburner_efficiency: k*self.afterburner_efficiency,
+            power_turbine_efficiency: k*self.power_turbine_efficiency,
+            nozzle_pressure_ratio: k*self.nozzle_pressure_ratio,
+            propeller_efficiency: k*self.propeller_efficiency,
+            propeller_diameter: k*self.propeller_diameter,
+            flight_mach_number: k*self.flight_mach_number,
+            burner_mach_number: k*self.burner_mach_number,
+            burner_exit_temp: k*self.burner_exit_temp,
+            ngv_cooling_fraction: k*self.ngv_cooling_fraction,
+            rotor_cooling_fraction: k*self.rotor_cooling_fraction,
+            blade_metal_temp: k*self.blade_metal_temp,
+            cooling_flow_factor: k*self.cooling_flow_factor,
+            customer_bleed_fraction: k*self.customer_bleed_fraction,
+            hp_power_offtake: k*self.hp_power_offtake,
+            lp_power_offtake: k*self.lp_power_offtake,
         }
     }
 }
 
+impl From<&str> for Variables {
+    fn from(text: &str) -> Self {
+        text.parse().unwrap_or_else(|error| {
+            println!("[FATAL] {}", error);
+            process::exit(0);
+        })
+    }
+}
+
+impl FromStr for Variables {
+    type Err = String;
+
+    /// Parses a design written as a `.jetlab` file.
+    ///
+    /// Variables not given keep their values from the Vanilla Plus baseline.
+    fn from_str(text: &str) -> std::result::Result<Self, Self::Err> {
+        let mut variables = VANILLA_PLUS;
+
+        // Remove comments
+        let text = text.lines()
+            .map(|line| line.split("//").next().unwrap_or_default())
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        let body = match (text.find('{'), text.rfind('}')) {
+            (Some (start), Some (end)) if start < end => &text[start + 1..end],
+            _ => return Err ("Could not parse design.  Use `Variables { variable: value, ... }`.".to_string()),
+        };
+
+        for field in body.split(',').map(str::trim).filter(|field| !field.is_empty()) {
+            let (key, value) = field.split_once(':')
+                .ok_or_else(|| format!("Could not parse {} in design.  Use `variable: value`.", field))?;
+
+            let var: VarSelector = key.trim().parse()?;
+            *variables.get_reference(var) = str::parse::<f64>(value.trim())
+                .map_err(|_| format!("Could not parse {} as numeric value for {}", value.trim(), key.trim()))?;
+        }
+
+        Ok (variables)
+    }
+}
+
 impl Display for Variables {
     /// Format this data structure.
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
@@ -147,8 +324,12 @@ impl Display for Variables {
         output.push_str(&format!("Fan pressure ratio: {:.6}\n", self.fan_pressure_ratio));
         output.push_str(&format!("Fan efficiency: {:.6}%\n", self.fan_efficiency*100.0));
         output.push_str(&format!("Fan bypass ratio: {:.6}\n", self.fan_bypass));
+        output.push_str(&format!("Gear ratio: {:.6}\n", self.gear_ratio));
+        output.push_str(&format!("Gearbox efficiency: {:.6}%\n", self.gearbox_efficiency*100.0));
         output.push_str(&format!("LPC pressure ratio: {:.6}\n", self.lpc_pressure_ratio));
         output.push_str(&format!("LPC efficiency: {:.6}%\n", self.lpc_efficiency*100.0));
+        output.push_str(&format!("IPC pressure ratio: {:.6}\n", self.ipc_pressure_ratio));
+        output.push_str(&format!("IPC efficiency: {:.6}%\n", self.ipc_efficiency*100.0));
         output.push_str(&format!("HPC pressure ratio: {:.6}\n", self.hpc_pressure_ratio));
         output.push_str(&format!("HPC efficiency: {:.6}%\n", self.hpc_efficiency*100.0));
         output.push_str(&format!("HPC discharge temperature: {:.6} K\n", self.hpc_discharge_temp));
@@ -156,10 +337,30 @@ impl Display for Variables {
         output.push_str(&format!("Combustor efficiency: {:.6}%\n", self.combustor_efficiency*100.0));
         output.push_str(&format!("HPT inlet temperature: {:.6} K\n", self.hpt_inlet_temp));
         output.push_str(&format!("HPT efficiency: {:.6}%\n", self.hpt_efficiency*100.0));
+        output.push_str(&format!("IPT efficiency: {:.6}%\n", self.ipt_efficiency*100.0));
         output.push_str(&format!("LPT efficiency: {:.6}%\n", self.lpt_efficiency*100.0));
         output.push_str(&format!("Bypass duct pressure recovery coefficient: {:.6}\n", self.bypass_pressure_recovery));
         output.push_str(&format!("Fuel enthalpy: {:.6} J/kg\n", self.fuel_delta_h));
         output.push_str(&format!("Fuel specific heat capacity at constant pressure: {:.6} J/kg-K\n", self.fuel_cp));
+        output.push_str(&format!("Mixer Mach number: {:.6}\n", self.mixer_mach_number));
+        output.push_str(&format!("Mixer efficiency: {:.6}%\n", self.mixer_efficiency*100.0));
+        output.push_str(&format!("Afterburner exit temperature: {:.6} K\n", self.afterburner_exit_temp));
+        output.push_str(&format!("Afterburner pressure recovery coefficient: {:.6}\n", self.afterburner_pressure_recovery));
+        output.push_str(&format!("Afterburner efficiency: {:.6}%\n", self.afterburner_efficiency*100.0));
+        output.push_str(&format!("Power turbine efficiency: {:.6}%\n", self.power_turbine_efficiency*100.0));
+        output.push_str(&format!("Nozzle pressure ratio: {:.6}\n", self.nozzle_pressure_ratio));
+        output.push_str(&format!("Propeller efficiency: {:.6}%\n", self.propeller_efficiency*100.0));
+        output.push_str(&format!("Propeller diameter: {:.6} m\n", self.propeller_diameter));
+        output.push_str(&format!("Flight Mach number: {:.6}\n", self.flight_mach_number));
+        output.push_str(&format!("Burner Mach number: {:.6}\n", self.burner_mach_number));
+        output.push_str(&format!("Burner exit temperature: {:.6} K\n", self.burner_exit_temp));
+        output.push_str(&format!("NGV cooling fraction: {:.6}%\n", self.ngv_cooling_fraction*100.0));
+        output.push_str(&format!("Rotor cooling fraction: {:.6}%\n", self.rotor_cooling_fraction*100.0));
+        output.push_str(&format!("Blade metal temperature: {:.6} K\n", self.blade_metal_temp));
+        output.push_str(&format!("Cooling flow factor: {:.6}\n", self.cooling_flow_factor));
+        output.push_str(&format!("Customer bleed fraction: {:.6}%\n", self.customer_bleed_fraction*100.0));
+        output.push_str(&format!("HP power offtake: {:.6} kW\n", self.hp_power_offtake));
+        output.push_str(&format!("LP power offtake: {:.6} kW\n", self.lp_power_offtake));
 
         write!(f, "{}", output)
     }
@@ -170,25 +371,75 @@ impl Add for Variables {
 
     fn add(self, other: Self) -> Self {
         Self {
-            inlet_mach_number: (self.inlet_mach_number + other.inlet_mach_number).clamp(INLET_MACH_NUMBER_MIN, INLET_MACH_NUMBER_MAX),
-            inlet_diameter: (self.inlet_diameter + other.inlet_diameter).clamp(INLET_DIAMETER_MIN, INLET_DIAMETER_MAX),
+            inlet_mach_number: self.inlet_mach_number + other.inlet_mach_number,
+            inlet_diameter: self.inlet_diameter + other.inlet_diameter,
             inlet_efficiency: self.inlet_efficiency + other.inlet_efficiency,
-            fan_pressure_ratio: (self.fan_pressure_ratio + other.fan_pressure_ratio).clamp(FAN_PRESSURE_RATIO_MIN, FAN_PRESSURE_RATIO_MAX),
+            fan_pressure_ratio: self.fan_pressure_ratio + other.fan_pressure_ratio,
             fan_efficiency: self.fan_efficiency + other.fan_efficiency,
-            fan_bypass: (self.fan_bypass + other.fan_bypass).clamp(FAN_BYPASS_MIN, FAN_BYPASS_MAX),
-            lpc_pressure_ratio: (self.lpc_pressure_ratio + other.lpc_pressure_ratio).clamp(LPC_PRESSURE_RATIO_MIN, LPC_PRESSURE_RATIO_MAX),
+            fan_bypass: self.fan_bypass + other.fan_bypass,
+            gear_ratio: self.gear_ratio + other.gear_ratio,
+            gearbox_efficiency: self.gearbox_efficiency + other.gearbox_efficiency,
+            lpc_pressure_ratio: self.lpc_pressure_ratio + other.lpc_pressure_ratio,
             lpc_efficiency: self.lpc_efficiency + other.lpc_efficiency,
-            hpc_pressure_ratio: (self.hpc_pressure_ratio + other.hpc_pressure_ratio).clamp(HPC_PRESSURE_RATIO_MIN, HPC_PRESSURE_RATIO_MAX),
+            ipc_pressure_ratio: self.ipc_pressure_ratio + other.ipc_pressure_ratio,
+            ipc_efficiency: self.ipc_efficiency + other.ipc_efficiency,
+            hpc_pressure_ratio: self.hpc_pressure_ratio + other.hpc_pressure_ratio,
             hpc_efficiency: self.hpc_efficiency + other.hpc_efficiency,
-            hpc_discharge_temp: (self.hpc_discharge_temp + other.hpc_discharge_temp).clamp(HPC_DISCHARGE_TEMP_MIN, HPC_DISCHARGE_TEMP_MAX),
+            hpc_discharge_temp: self.hpc_discharge_temp + other.hpc_discharge_temp,
             combustor_pressure_recovery: self.combustor_pressure_recovery + other.combustor_pressure_recovery,
             combustor_efficiency: self.combustor_efficiency + other.combustor_efficiency,
-            hpt_inlet_temp: (self.hpt_inlet_temp + other.hpt_inlet_temp).clamp(HPT_INLET_TEMP_MIN, HPT_INLET_TEMP_MAX),
+            hpt_inlet_temp: self.hpt_inlet_temp + other.hpt_inlet_temp,
             hpt_efficiency: self.hpt_efficiency + other.hpt_efficiency,
+            ipt_efficiency: self.ipt_efficiency + other.ipt_efficiency,
             lpt_efficiency: self.lpt_efficiency + other.lpt_efficiency,
             bypass_pressure_recovery: self.bypass_pressure_recovery + other.bypass_pressure_recovery,
             fuel_delta_h: self.fuel_delta_h + other.fuel_delta_h,
             fuel_cp: self.fuel_cp + other.fuel_cp,
+            mixer_mach_number: self.mixer_mach_number + other.mixer_mach_number,
+            mixer_efficiency: self.mixer_efficiency + other.mixer_efficiency,
+            afterburner_exit_temp: self.afterburner_exit_temp + other.afterburner_exit_temp,
+            afterburner_pressure_recovery: self.afterburner_pressure_recovery + other.afterburner_pressure_recovery,
+            afterburner_efficiency: self.afterburner_efficiency + other.afterburner_efficiency,
+            power_turbine_efficiency: self.power_turbine_efficiency + other.power_turbine_efficiency,
+            nozzle_pressure_ratio: self.nozzle_pressure_ratio + other.nozzle_pressure_ratio,
+            propeller_efficiency: self.propeller_efficiency + other.propeller_efficiency,
+            propeller_diameter: self.propeller_diameter + other.propeller_diameter,
+            flight_mach_number: self.flight_mach_number + other.flight_mach_number,
+            burner_mach_number: self.burner_mach_number + other.burner_mach_number,
+            burner_exit_temp: self.burner_exit_temp + other.burner_exit_temp,
+            ngv_cooling_fraction: self.ngv_cooling_fraction + other.ngv_cooling_fraction,
+            rotor_cooling_fraction: self.rotor_cooling_fraction + other.rotor_cooling_fraction,
+            blade_metal_temp: self.blade_metal_temp + other.blade_metal_temp,
+            cooling_flow_factor: self.cooling_flow_factor + other.cooling_flow_factor,
+            customer_bleed_fraction: self.customer_bleed_fraction + other.customer_bleed_fraction,
+            hp_power_offtake: self.hp_power_offtake + other.hp_power_offtake,
+            lp_power_offtake: self.lp_power_offtake + other.lp_power_offtake,
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bundled_design() {
+        let parsed: Variables = include_str!("vanilla-plus-3.jetlab").parse().unwrap();
+
+        assert_eq!(parsed.to_string(), VANILLA_PLUS_THREE_SPOOL.to_string());
+    }
+
+    #[test]
+    fn keeps_baseline_for_missing_variables() {
+        let parsed: Variables = "Variables { fan_bypass: 8.0, // comment\n }".parse().unwrap();
+
+        assert_eq!(parsed.fan_bypass, 8.0);
+        assert_eq!(parsed.hpc_pressure_ratio, VANILLA_PLUS.hpc_pressure_ratio);
+    }
+
+    #[test]
+    fn rejects_bad_designs() {
+        let error = "Variables { fan_bypas: 8.0 }".parse::<Variables>().unwrap_err();
+        assert_eq!(error, "Invalid variable name fan_bypas.  Type `jetlab` for help.");
+    }
 }
\ No newline at end of file
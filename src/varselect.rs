@@ -1,8 +1,11 @@
 //! Allows the user to select for a single variable.
 
-use std::process;
+use std::{
+    process,
+    str::FromStr,
+};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 /// Enumerates the variables the user may select.
 pub enum VarSelector {
     InletMachNumber,
@@ -11,8 +14,12 @@ pub enum VarSelector {
     FanPressureRatio,
     FanEfficiency,
     FanBypass,
+    GearRatio,
+    GearboxEfficiency,
     LpcPressureRatio,
     LpcEfficiency,
+    IpcPressureRatio,
+    IpcEfficiency,
     HpcPressureRatio,
     HpcEfficiency,
     HpcDischargeTemp,
@@ -20,10 +27,30 @@ pub enum VarSelector {
     CombustorEfficiency,
     HptInletTemp,
     HptEfficiency,
+    IptEfficiency,
     LptEfficiency,
     BypassPressureRecovery,
     FuelDeltaH,
     FuelCp,
+    MixerMachNumber,
+    MixerEfficiency,
+    AfterburnerExitTemp,
+    AfterburnerPressureRecovery,
+    AfterburnerEfficiency,
+    PowerTurbineEfficiency,
+    NozzlePressureRatio,
+    PropellerEfficiency,
+    PropellerDiameter,
+    FlightMachNumber,
+    BurnerMachNumber,
+    BurnerExitTemp,
+    NgvCoolingFraction,
+    RotorCoolingFraction,
+    BladeMetalTemp,
+    CoolingFlowFactor,
+    CustomerBleedFraction,
+    HpPowerOfftake,
+    LpPowerOfftake,
 }
 
 use VarSelector::*;
@@ -37,8 +64,12 @@ impl From<VarSelector> for String {
             FanPressureRatio            => "Fan Pressure Ratio",
             FanEfficiency               => "Fan Efficiency",
             FanBypass                   => "Fan Bypass Ratio",
+            GearRatio                   => "Gear Ratio",
+            GearboxEfficiency           => "Gearbox Efficiency",
             LpcPressureRatio            => "LPC Pressure Ratio",
             LpcEfficiency               => "LPC Efficiency",
+            IpcPressureRatio            => "IPC Pressure Ratio",
+            IpcEfficiency               => "IPC Efficiency",
             HpcPressureRatio            => "HPC Pressure Ratio",
             HpcEfficiency               => "HPC Efficiency",
             HpcDischargeTemp            => "HPC Discharge Temperature (K)",
@@ -46,10 +77,30 @@ impl From<VarSelector> for String {
             CombustorEfficiency         => "Combustor Efficiency",
             HptInletTemp                => "HPT Inlet Temperature (K)",
             HptEfficiency               => "HPT Efficiency",
+            IptEfficiency               => "IPT Efficiency",
             LptEfficiency               => "LPT Efficiency",
             BypassPressureRecovery      => "Bypass Pressure Recovery Coefficient",
             FuelDeltaH                  => "Fuel Enthalpy (J/kg)",
             FuelCp                      => "Fuel CP (J/kg-K)",
+            MixerMachNumber             => "Mixer Mach Number",
+            MixerEfficiency             => "Mixer Efficiency",
+            AfterburnerExitTemp         => "Afterburner Exit Temperature (K)",
+            AfterburnerPressureRecovery => "Afterburner Pressure Recovery Coefficient",
+            AfterburnerEfficiency       => "Afterburner Efficiency",
+            PowerTurbineEfficiency      => "Power Turbine Efficiency",
+            NozzlePressureRatio         => "Nozzle Pressure Ratio",
+            PropellerEfficiency         => "Propeller Efficiency",
+            PropellerDiameter           => "Propeller Diameter (m)",
+            FlightMachNumber            => "Flight Mach Number",
+            BurnerMachNumber            => "Burner Mach Number",
+            BurnerExitTemp              => "Burner Exit Temperature (K)",
+            NgvCoolingFraction          => "NGV Cooling Fraction",
+            RotorCoolingFraction        => "Rotor Cooling Fraction",
+            BladeMetalTemp              => "Blade Metal Temperature (K)",
+            CoolingFlowFactor           => "Cooling Flow Factor",
+            CustomerBleedFraction       => "Customer Bleed Fraction",
+            HpPowerOfftake              => "HP Power Offtake (kW)",
+            LpPowerOfftake              => "LP Power Offtake (kW)",
         };
 
         string.to_string()
@@ -58,15 +109,31 @@ impl From<VarSelector> for String {
 
 impl From<String> for VarSelector {
     fn from(string: String) -> Self {
-        match string.as_str() {
+        string.parse().unwrap_or_else(|error| {
+            println!("[FATAL] {}", error);
+            process::exit(0);
+        })
+    }
+}
+
+impl FromStr for VarSelector {
+    type Err = String;
+
+    /// Parses the command-line name of a variable.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Ok (match string {
             "inlet_mach_number" => InletMachNumber,
             "inlet_diameter" => InletDiameter,
             "inlet_efficiency" => InletEfficiency,
             "fan_pressure_ratio" => FanPressureRatio,
             "fan_efficiency" => FanEfficiency,
             "fan_bypass" => FanBypass,
+            "gear_ratio" => GearRatio,
+            "gearbox_efficiency" => GearboxEfficiency,
             "lpc_pressure_ratio" => LpcPressureRatio,
             "lpc_efficiency" => LpcEfficiency,
+            "ipc_pressure_ratio" => IpcPressureRatio,
+            "ipc_efficiency" => IpcEfficiency,
             "hpc_pressure_ratio" => HpcPressureRatio,
             "hpc_efficiency" => HpcEfficiency,
             "hpc_discharge_temp" => HpcDischargeTemp,
@@ -74,14 +141,82 @@ impl From<String> for VarSelector {
             "combustor_efficiency" => CombustorEfficiency,
             "hpt_inlet_temp" => HptInletTemp,
             "hpt_efficiency" => HptEfficiency,
+            "ipt_efficiency" => IptEfficiency,
             "lpt_efficiency" => LptEfficiency,
             "bypass_pressure_recovery" => BypassPressureRecovery,
             "fuel_delta_h" => FuelDeltaH,
             "fuel_cp" => FuelCp,
-            _ => {
-                println!("[FATAL] Invalid variable name {}.  Type `jetlab` for help.", &string);
-                process::exit(0);
-            },
+            "mixer_mach_number" => MixerMachNumber,
+            "mixer_efficiency" => MixerEfficiency,
+            "afterburner_exit_temp" => AfterburnerExitTemp,
+            "afterburner_pressure_recovery" => AfterburnerPressureRecovery,
+            "afterburner_efficiency" => AfterburnerEfficiency,
+            "power_turbine_efficiency" => PowerTurbineEfficiency,
+            "nozzle_pressure_ratio" => NozzlePressureRatio,
+            "propeller_efficiency" => PropellerEfficiency,
+            "propeller_diameter" => PropellerDiameter,
+            "flight_mach_number" => FlightMachNumber,
+            "burner_mach_number" => BurnerMachNumber,
+            "burner_exit_temp" => BurnerExitTemp,
+            "ngv_cooling_fraction" => NgvCoolingFraction,
+            "rotor_cooling_fraction" => RotorCoolingFraction,
+            "blade_metal_temp" => BladeMetalTemp,
+            "cooling_flow_factor" => CoolingFlowFactor,
+            "customer_bleed_fraction" => CustomerBleedFraction,
+            "hp_power_offtake" => HpPowerOfftake,
+            "lp_power_offtake" => LpPowerOfftake,
+            _ => return Err (format!("Invalid variable name {}.  Type `jetlab` for help.", string)),
+        })
+    }
+}
+
+impl VarSelector {
+    /// Gives the command-line name of this variable.
+    pub fn key(self) -> &'static str {
+        match self {
+            InletMachNumber             => "inlet_mach_number",
+            InletDiameter               => "inlet_diameter",
+            InletEfficiency             => "inlet_efficiency",
+            FanPressureRatio            => "fan_pressure_ratio",
+            FanEfficiency               => "fan_efficiency",
+            FanBypass                   => "fan_bypass",
+            GearRatio                   => "gear_ratio",
+            GearboxEfficiency           => "gearbox_efficiency",
+            LpcPressureRatio            => "lpc_pressure_ratio",
+            LpcEfficiency               => "lpc_efficiency",
+            IpcPressureRatio            => "ipc_pressure_ratio",
+            IpcEfficiency               => "ipc_efficiency",
+            HpcPressureRatio            => "hpc_pressure_ratio",
+            HpcEfficiency               => "hpc_efficiency",
+            HpcDischargeTemp            => "hpc_discharge_temp",
+            CombustorPressureRecovery   => "combustor_pressure_recovery",
+            CombustorEfficiency         => "combustor_efficiency",
+            HptInletTemp                => "hpt_inlet_temp",
+            HptEfficiency               => "hpt_efficiency",
+            IptEfficiency               => "ipt_efficiency",
+            LptEfficiency               => "lpt_efficiency",
+            BypassPressureRecovery      => "bypass_pressure_recovery",
+            FuelDeltaH                  => "fuel_delta_h",
+            FuelCp                      => "fuel_cp",
+            MixerMachNumber             => "mixer_mach_number",
+            MixerEfficiency             => "mixer_efficiency",
+            AfterburnerExitTemp         => "afterburner_exit_temp",
+            AfterburnerPressureRecovery => "afterburner_pressure_recovery",
+            AfterburnerEfficiency       => "afterburner_efficiency",
+            PowerTurbineEfficiency      => "power_turbine_efficiency",
+            NozzlePressureRatio         => "nozzle_pressure_ratio",
+            PropellerEfficiency         => "propeller_efficiency",
+            PropellerDiameter           => "propeller_diameter",
+            FlightMachNumber            => "flight_mach_number",
+            BurnerMachNumber            => "burner_mach_number",
+            BurnerExitTemp              => "burner_exit_temp",
+            NgvCoolingFraction          => "ngv_cooling_fraction",
+            RotorCoolingFraction        => "rotor_cooling_fraction",
+            BladeMetalTemp              => "blade_metal_temp",
+            CoolingFlowFactor           => "cooling_flow_factor",
+            CustomerBleedFraction       => "customer_bleed_fraction",
+            HpPowerOfftake              => "hp_power_offtake",
+            LpPowerOfftake              => "lp_power_offtake",
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,258 @@
+//! Estimates the dry weight and dimensions of an engine from its design.
+//!
+//! Each turbomachine is sized by its stage count and mean annulus diameter.
+//! Compressor stage counts follow from a limiting stage pressure ratio, and
+//! turbine stage counts from a limiting stage loading `dh / U^2` at the mean
+//! blade speed of the spool.  Module weights scale with the square of diameter
+//! and module lengths with diameter, per stage; the coefficients are rough
+//! statistical values for current civil turbofans.
+
+use std::fmt::{
+    self,
+    Display,
+    Formatter,
+};
+
+use crate::{
+    constants::*,
+    flow::flow_area,
+    Cycle,
+};
+
+/// Largest total pressure ratio of one fan stage.
+const FAN_STAGE_PRESSURE_RATIO: f64 = 1.9;
+
+/// Weight of one fan stage and its containment case (in kg/m2 of tip diameter squared).
+const FAN_STAGE_MASS: f64 = 300.0;
+
+/// Axial length of one fan stage (per m of tip diameter).
+const FAN_STAGE_LENGTH: f64 = 0.35;
+
+/// Largest total pressure ratio of one LPC (booster) stage.
+pub(crate) const LPC_STAGE_PRESSURE_RATIO: f64 = 1.25;
+
+/// Largest total pressure ratio of one IPC stage.
+pub(crate) const IPC_STAGE_PRESSURE_RATIO: f64 = 1.3;
+
+/// Largest total pressure ratio of one HPC stage.
+pub(crate) const HPC_STAGE_PRESSURE_RATIO: f64 = 1.4;
+
+/// Weight of one compressor stage (in kg/m2 of mean diameter squared).
+const COMPRESSOR_STAGE_MASS: f64 = 150.0;
+
+/// Axial length of one compressor stage (per m of mean diameter).
+const COMPRESSOR_STAGE_LENGTH: f64 = 0.15;
+
+/// Largest stage loading `dh / U^2` of one turbine stage.
+const TURBINE_STAGE_LOADING: f64 = 2.0;
+
+/// Weight of one turbine stage (in kg/m2 of mean diameter squared).
+const TURBINE_STAGE_MASS: f64 = 250.0;
+
+/// Axial length of one turbine stage (per m of mean diameter).
+const TURBINE_STAGE_LENGTH: f64 = 0.2;
+
+/// Mean blade speed of the HPT (in m/s).
+pub(crate) const HPT_BLADE_SPEED: f64 = 450.0;
+
+/// Mean blade speed of the IPT (in m/s).
+pub(crate) const IPT_BLADE_SPEED: f64 = 400.0;
+
+/// Mean blade speed of a directly driven LPT (in m/s).
+pub(crate) const LPT_BLADE_SPEED: f64 = 250.0;
+
+/// Largest mean blade speed of a geared LPT (in m/s).
+pub(crate) const MAX_LPT_BLADE_SPEED: f64 = 400.0;
+
+/// Weight of the combustor (in kg/m2 of HPC exit diameter squared).
+const COMBUSTOR_MASS: f64 = 120.0;
+
+/// Axial length of the combustor (per m of HPC exit diameter).
+const COMBUSTOR_LENGTH: f64 = 0.8;
+
+/// Weight of the afterburner duct (in kg/m2 of LPT exit diameter squared).
+const AFTERBURNER_MASS: f64 = 200.0;
+
+/// Axial length of the afterburner duct (per m of LPT exit diameter).
+const AFTERBURNER_LENGTH: f64 = 1.5;
+
+/// Weight of a fan reduction gearbox (in kg/kW of fan power).
+const GEARBOX_SPECIFIC_MASS: f64 = 0.004;
+
+/// Weight of shafts, frames, casings and accessories, as a fraction of module weight.
+const STRUCTURE_FRACTION: f64 = 0.6;
+
+/// Ratio of outer casing diameter to blade tip diameter.
+const CASING_RATIO: f64 = 1.08;
+
+/// Axial Mach number at compressor faces.
+const COMPRESSOR_MACH: f64 = 0.5;
+
+/// Axial Mach number at turbine faces.
+const TURBINE_MACH: f64 = 0.4;
+
+#[derive(Clone, Copy, Debug)]
+/// Holds the estimated size of one engine module.
+pub struct Module {
+    /// Module name (e.g. "HPC")
+    pub name: &'static str,
+
+    /// Number of stages
+    pub stages: usize,
+
+    /// Mean tip diameter (in m)
+    pub diameter: f64,
+
+    /// Axial length (in m)
+    pub length: f64,
+
+    /// Weight (in kg)
+    pub weight: f64,
+}
+
+/// Computes the tip diameter (in m) of an annulus passing a mass flow at a given hub-to-tip ratio.
+fn annulus_diameter(mdot: f64, t_t: f64, p_t: f64, mach: f64, gamma: f64, hub_tip: f64) -> f64 {
+    let area = flow_area(mdot, t_t, p_t, mach, gamma);
+
+    (4.0 * area / (PI * (1.0 - hub_tip.powi(2)))).sqrt()
+}
+
+impl Module {
+    /// Sizes a fan of a given tip diameter (in m) and pressure ratio.
+    pub fn fan(diameter: f64, pressure_ratio: f64) -> Self {
+        let stages = (pressure_ratio.ln() / FAN_STAGE_PRESSURE_RATIO.ln()).ceil().max(1.0) as usize;
+
+        Self {
+            name: "Fan",
+            stages,
+            diameter,
+            length: stages as f64 * FAN_STAGE_LENGTH * diameter,
+            weight: stages as f64 * FAN_STAGE_MASS * diameter.powi(2),
+        }
+    }
+
+    /// Sizes a compressor passing `mdot` (in kg/s) between two stations, with a limiting stage pressure ratio.
+    pub fn compressor(name: &'static str, mdot: f64, inlet: (f64, f64), exit: (f64, f64), stage_pressure_ratio: f64) -> Self {
+        let pressure_ratio = exit.1 / inlet.1;
+        let stages = (pressure_ratio.ln() / stage_pressure_ratio.ln()).ceil().max(1.0) as usize;
+
+        let d_in = annulus_diameter(mdot, inlet.0, inlet.1, COMPRESSOR_MACH, GAMMA, 0.5);
+        let d_out = annulus_diameter(mdot, exit.0, exit.1, COMPRESSOR_MACH, GAMMA, 0.9);
+        let diameter = 0.5*(d_in + d_out);
+
+        Self {
+            name,
+            stages,
+            diameter,
+            length: stages as f64 * COMPRESSOR_STAGE_LENGTH * diameter,
+            weight: stages as f64 * COMPRESSOR_STAGE_MASS * diameter.powi(2),
+        }
+    }
+
+    /// Sizes a turbine passing `mdot` (in kg/s) between two stations at a given mean blade speed (in m/s).
+    pub fn turbine(name: &'static str, mdot: f64, inlet: (f64, f64), exit: (f64, f64), blade_speed: f64) -> Self {
+        let cp = GAMMA_FLUE * R / (GAMMA_FLUE - 1.0);
+        let work = cp * (inlet.0 - exit.0);
+        let stages = (work / (TURBINE_STAGE_LOADING * blade_speed.powi(2))).ceil().max(1.0) as usize;
+
+        let d_in = annulus_diameter(mdot, inlet.0, inlet.1, TURBINE_MACH, GAMMA_FLUE, 0.85);
+        let d_out = annulus_diameter(mdot, exit.0, exit.1, TURBINE_MACH, GAMMA_FLUE, 0.65);
+        let diameter = 0.5*(d_in + d_out);
+
+        Self {
+            name,
+            stages,
+            diameter,
+            length: stages as f64 * TURBINE_STAGE_LENGTH * diameter,
+            weight: stages as f64 * TURBINE_STAGE_MASS * diameter.powi(2),
+        }
+    }
+
+    /// Sizes a combustor behind an HPC of a given exit diameter (in m).
+    pub fn combustor(diameter: f64) -> Self {
+        Self {
+            name: "Combustor",
+            stages: 0,
+            diameter,
+            length: COMBUSTOR_LENGTH * diameter,
+            weight: COMBUSTOR_MASS * diameter.powi(2),
+        }
+    }
+
+    /// Sizes an afterburner duct behind an LPT of a given exit diameter (in m).
+    pub fn afterburner(diameter: f64) -> Self {
+        Self {
+            name: "Afterburner",
+            stages: 0,
+            diameter,
+            length: AFTERBURNER_LENGTH * diameter,
+            weight: AFTERBURNER_MASS * diameter.powi(2),
+        }
+    }
+
+    /// Sizes a fan reduction gearbox transmitting a given fan power (in kW).
+    pub fn gearbox(power: f64) -> Self {
+        Self {
+            name: "Gearbox",
+            stages: 0,
+            diameter: 0.0,
+            length: 0.0,
+            weight: GEARBOX_SPECIFIC_MASS * power,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Holds the estimated dry weight and dimensions of an engine.
+pub struct Size {
+    /// Engine modules, from front to back
+    pub modules: Vec<Module>,
+
+    /// Dry weight, including structure and accessories (in kg)
+    pub weight: f64,
+
+    /// Length from fan face to LPT exit (in m)
+    pub length: f64,
+
+    /// Maximum casing diameter (in m)
+    pub diameter: f64,
+}
+
+impl Size {
+    /// Totals the weight and dimensions of a set of modules.
+    pub fn new(modules: Vec<Module>) -> Self {
+        let weight = (1.0 + STRUCTURE_FRACTION) * modules.iter().map(|m| m.weight).sum::<f64>();
+        let length = modules.iter().map(|m| m.length).sum();
+        let diameter = CASING_RATIO * modules.iter().map(|m| m.diameter).fold(0.0, f64::max);
+
+        Self {
+            modules,
+            weight,
+            length,
+            diameter,
+        }
+    }
+}
+
+impl Display for Size {
+    /// Format this data structure.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+
+        output.push_str(&format!("{:<12}{:>8}{:>16}{:>12}{:>14}\n", "Module", "Stages", "Diameter (m)", "Length (m)", "Weight (kg)"));
+        for module in &self.modules {
+            output.push_str(&format!("{:<12}{:>8}{:>16.3}{:>12.3}{:>14.1}\n", module.name, module.stages, module.diameter, module.length, module.weight));
+        }
+
+        output.push_str(&format!("\nDry weight: {:.1} kg\n", self.weight));
+        output.push_str(&format!("Length: {:.3} m\n", self.length));
+        output.push_str(&format!("Maximum diameter: {:.3} m\n", self.diameter));
+
+        write!(f, "{}", output)
+    }
+}
+
+/// Finds the total temperature and pressure at a station of a cycle.
+pub(crate) fn station(cycle: &Cycle, name: &str) -> Option<(f64, f64)> {
+    cycle.stations.iter().find(|s| s.name == name).map(|s| (s.total_temp, s.total_pressure))
+}
\ No newline at end of file